@@ -0,0 +1,59 @@
+use core::ops::{Mul,MulAssign};
+use crate::{xy, vec2, mat3, Matrix, MinMax};
+#[allow(unused_imports)] use crate::Float;
+
+// 2D affine transform: [linear | translation] with an implicit [0 0 1] last row
+#[repr(C)] #[derive(Clone, Copy, Debug, PartialEq)] pub struct Affine2(pub Matrix<f32, 2, 3>);
+unsafe impl bytemuck::Zeroable for Affine2 {}
+unsafe impl bytemuck::Pod for Affine2 {}
+impl Default for Affine2 { fn default() -> Self { Self::IDENTITY } }
+
+impl Affine2 {
+	pub const IDENTITY: Self = Affine2(Matrix([[1.,0.,0.],[0.,1.,0.]]));
+	pub fn new(Matrix([[a,b],[c,d]]): Matrix<f32, 2, 2>, xy{x,y}: vec2) -> Self { Affine2(Matrix([[a,b,x],[c,d,y]])) }
+	pub fn linear(self) -> Matrix<f32, 2, 2> { let Matrix([[a,b,_],[c,d,_]]) = self.0; Matrix([[a,b],[c,d]]) }
+	pub fn translation(self) -> vec2 { let Matrix([[_,_,x],[_,_,y]]) = self.0; xy{x,y} }
+	pub fn translate(t: vec2) -> Self { Self::new(Matrix::identity(), t) }
+	pub fn scale(xy{x,y}: vec2) -> Self { Self::new(Matrix::diagonal([x,y]), num::zero()) }
+	// x += k.x*y, y += k.y*x
+	pub fn skew(k: vec2) -> Self { Self::new(Matrix([[1.,k.x],[k.y,1.]]), num::zero()) }
+	pub fn transform_point(self, p: vec2) -> vec2 { self.transform_vector(p) + self.translation() }
+	pub fn transform_vector(self, v: vec2) -> vec2 { self.linear() * v }
+	// Bounding box of the transformed corners
+	pub fn transform_rect(self, MinMax{min, max}: MinMax<vec2>) -> MinMax<vec2> {
+		crate::minmax([min, xy{x: max.x, y: min.y}, max, xy{x: min.x, y: max.y}].map(|p| self.transform_point(p))).unwrap()
+	}
+	pub fn det(self) -> f32 { self.linear().det() }
+	pub fn inverse(self) -> Option<Self> { let linear = self.linear().inverse()?; Some(Self::new(linear, -(linear * self.translation()))) }
+}
+impl Affine2 {
+	// Counterclockwise as crate::rotate
+	pub fn rotate(angle: f32) -> Self { let (s,c) = f32::sin_cos(angle); Self::new(Matrix([[c,-s],[s,c]]), num::zero()) }
+}
+
+// Applies b then self
+impl Mul for Affine2 { type Output = Self; fn mul(self, b: Self) -> Self { Self::new(self.linear()*b.linear(), self.transform_point(b.translation())) } }
+impl MulAssign for Affine2 { fn mul_assign(&mut self, b: Self) { *self = *self * b } }
+impl Mul<vec2> for Affine2 { type Output = vec2; fn mul(self, p: vec2) -> vec2 { self.transform_point(p) } }
+impl Mul<MinMax<vec2>> for Affine2 { type Output = MinMax<vec2>; fn mul(self, r: MinMax<vec2>) -> MinMax<vec2> { self.transform_rect(r) } }
+impl From<Affine2> for mat3 { fn from(Affine2(Matrix([r0,r1])): Affine2) -> Self { Matrix([r0,r1,[0.,0.,1.]]) } }
+
+#[cfg(test)] mod test {
+	use crate::{xy, xyz, vec2, mat3, MinMax, Affine2};
+	fn close(a: vec2, b: vec2) -> bool { (a-b).into_iter().all(|e| e.abs() < 1e-5) }
+	#[test] fn composition() {
+		let (t, s) = (Affine2::translate(xy{x: 1., y: 2.}), Affine2::scale(xy{x: 2., y: 3.})); let p = xy{x: 1., y: 1.};
+		assert_eq!((t*s)*p, xy{x: 3., y: 5.}); assert_eq!((s*t)*p, xy{x: 4., y: 9.}); // t*s scales then translates
+		let a = t * s * Affine2::skew(xy{x: 0.5, y: 0.}); assert!(close(a*p, t*(s*(Affine2::skew(xy{x: 0.5, y: 0.})*p))));
+		assert_eq!(a.transform_vector(xy{x: 1., y: 0.}), xy{x: 2., y: 0.}); assert_eq!(mat3::from(a) * xyz{x: 1., y: 1., z: 1.}, (a*p).xy1());
+		assert_eq!(mat3::from(t*s), mat3::from(t) * mat3::from(s)); assert!(close(Affine2::rotate(0.3)*p, crate::rotate(0.3, p)));
+		let mut m = t; m *= s; assert_eq!(m, t*s); assert_eq!(Affine2::IDENTITY*a, a); assert_eq!(a*Affine2::IDENTITY, a);
+		assert_eq!(a * MinMax{min: xy{x: 0., y: 0.}, max: xy{x: 1., y: 1.}}, MinMax{min: xy{x: 1., y: 2.}, max: xy{x: 4., y: 5.}});
+	}
+	#[test] fn inverse() {
+		let a = Affine2::translate(xy{x: 1., y: -2.}) * Affine2::rotate(0.7) * Affine2::scale(xy{x: 2., y: 0.5}); let p = xy{x: 0.3, y: -1.5};
+		let i = a.inverse().unwrap(); assert!(close(i*(a*p), p)); assert!(close((a*i)*p, p)); assert!(close((i*a).translation(), xy{x: 0., y: 0.}));
+		assert!((a.det() - 1.).abs() < 1e-5); assert!((i.det() - 1.).abs() < 1e-5);
+		assert_eq!(Affine2::scale(xy{x: 0., y: 1.}).inverse(), None); assert_eq!(Affine2::translate(xy{x: 3., y: 4.}).inverse(), Some(Affine2::translate(xy{x: -3., y: -4.})));
+	}
+}
@@ -0,0 +1,18 @@
+// Swizzle lists for swizzle.rs: every name over the components of each vector into each output vector, by move when each component is used at most once
+fn main() {
+	let vectors = ["xy", "xyz", "xyzw"];
+	let mut out = String::new();
+	for vector in vectors {
+		let components: Vec<char> = vector.chars().collect();
+		out += &format!("impl_swizzle!{{{vector}\n");
+		for output in vectors {
+			let names = (0..output.len()).fold(vec![String::new()], |names, _| names.iter().flat_map(|name| components.iter().map(move |&c| format!("{name}{c}"))).collect());
+			let list = |names: Vec<&String>| names.iter().map(|name| format!("{name} {}", name.chars().map(String::from).collect::<Vec<_>>().join(" "))).collect::<Vec<_>>().join(", ");
+			let (moves, copies): (Vec<_>, Vec<_>) = names.iter().partition(|name| name.chars().all(|c| name.matches(c).count() == 1));
+			out += &format!("\t{output} [{}] [{}]\n", list(moves), list(copies));
+		}
+		out += "}\n";
+	}
+	std::fs::write(std::path::Path::new(&std::env::var("OUT_DIR").unwrap()).join("swizzle.rs"), out).unwrap();
+	println!("cargo::rerun-if-changed=build.rs");
+}
@@ -0,0 +1,91 @@
+use crate::{xyz, vec3, vec4, mat3, Matrix};
+#[allow(unused_imports)] use crate::Float;
+
+// sRGB transfer function (IEC 61966-2-1) on [0,1] components
+pub fn srgb_to_linear(c: f32) -> f32 { if c <= 0.04045 { c / 12.92 } else { ((c + 0.055) / 1.055).powf(2.4) } }
+pub fn linear_to_srgb(c: f32) -> f32 { if c <= 0.0031308 { c * 12.92 } else { 1.055 * c.powf(1./2.4) - 0.055 } }
+// Approximations: absolute error < 2e-3 on [0,1]
+pub fn srgb_to_linear_fast(c: f32) -> f32 { c * (c * (c * 0.305_306 + 0.682_171_1) + 0.012_522_878) }
+pub fn linear_to_srgb_fast(c: f32) -> f32 { if c <= 0.0031308 { return c * 12.92 } let [s1, s2, s3] = { let s1 = c.sqrt(); let s2 = s1.sqrt(); [s1, s2, s2.sqrt()] }; 0.585_122_4*s1 + 0.783_140_4*s2 - 0.368_262_74*s3 }
+
+// Alpha is linear
+impl vec3 {
+	pub fn srgb_to_linear(self) -> Self { self.map(srgb_to_linear) }
+	pub fn linear_to_srgb(self) -> Self { self.map(linear_to_srgb) }
+}
+impl vec4 {
+	pub fn srgb_to_linear(self) -> Self { self.xyz().srgb_to_linear().xyz0().with_w(self.w) }
+	pub fn linear_to_srgb(self) -> Self { self.xyz().linear_to_srgb().xyz0().with_w(self.w) }
+	pub fn premultiply(self) -> Self { (self.w*self.xyz()).xyz0().with_w(self.w) }
+	// Transparent black for zero alpha
+	pub fn unpremultiply(self) -> Self { if self.w == 0. { num::zero() } else { (self.xyz()/self.w).xyz0().with_w(self.w) } }
+}
+
+// Relative luminance (Rec. 709 primaries) of linear RGB
+pub fn luminance(rgb: vec3) -> f32 { crate::dot(xyz{x: 0.2126, y: 0.7152, z: 0.0722}, rgb) }
+
+// Hue as a fraction of a turn: all components in [0,1]
+pub fn rgb_to_hsv(xyz{x: r, y: g, z: b}: vec3) -> vec3 {
+	let max = r.max(g).max(b); let d = max - r.min(g).min(b);
+	xyz{x: hue(r, g, b, max, d), y: if max > 0. { d / max } else { 0. }, z: max}
+}
+pub fn hsv_to_rgb(xyz{x: h, y: s, z: v}: vec3) -> vec3 {
+	let f = |n: f32| { let k = (n + h*6.) % 6.; v - v*s*k.min(4.-k).clamp(0., 1.) };
+	xyz{x: f(5.), y: f(3.), z: f(1.)}
+}
+pub fn rgb_to_hsl(xyz{x: r, y: g, z: b}: vec3) -> vec3 {
+	let max = r.max(g).max(b); let min = r.min(g).min(b); let d = max - min; let l = (max + min) / 2.;
+	xyz{x: hue(r, g, b, max, d), y: if d > 0. { d / (1. - (2.*l - 1.).abs()) } else { 0. }, z: l}
+}
+pub fn hsl_to_rgb(xyz{x: h, y: s, z: l}: vec3) -> vec3 {
+	let a = s * l.min(1.-l);
+	let f = |n: f32| { let k = (n + h*12.) % 12.; l - a*(k-3.).min(9.-k).clamp(-1., 1.) };
+	xyz{x: f(0.), y: f(8.), z: f(4.)}
+}
+fn hue(r: f32, g: f32, b: f32, max: f32, d: f32) -> f32 {
+	if d == 0. { return 0. }
+	let h = if max == r { (g - b) / d } else if max == g { (b - r) / d + 2. } else { (r - g) / d + 4. };
+	(if h < 0. { h + 6. } else { h }) / 6.
+}
+
+// Linear sRGB to CIE 1931 XYZ (D65 white point)
+pub const LINEAR_SRGB_TO_XYZ: mat3 = Matrix([[0.4124564, 0.3575761, 0.1804375], [0.2126729, 0.7151522, 0.072175], [0.0193339, 0.119192, 0.9503041]]);
+pub const XYZ_TO_LINEAR_SRGB: mat3 = Matrix([[3.2404542, -1.5371385, -0.4985314], [-0.969266, 1.8760108, 0.041556], [0.0556434, -0.2040259, 1.0572252]]);
+pub fn linear_srgb_to_xyz(rgb: vec3) -> vec3 { LINEAR_SRGB_TO_XYZ * rgb }
+pub fn xyz_to_linear_srgb(xyz: vec3) -> vec3 { XYZ_TO_LINEAR_SRGB * xyz }
+
+// Oklab (Björn Ottosson) from linear sRGB
+const LINEAR_SRGB_TO_LMS: mat3 = Matrix([[0.41222147, 0.53633254, 0.051445993], [0.2119035, 0.6806995, 0.10739696], [0.08830246, 0.28171884, 0.6299787]]);
+const LMS_TO_OKLAB: mat3 = Matrix([[0.21045426, 0.7936178, -0.004072047], [1.9779985, -2.4285922, 0.4505937], [0.025904037, 0.78277177, -0.80867577]]);
+const OKLAB_TO_LMS: mat3 = Matrix([[1., 0.39633778, 0.21580376], [1., -0.105561346, -0.06385417], [1., -0.08948418, -1.2914855]]);
+const LMS_TO_LINEAR_SRGB: mat3 = Matrix([[4.0767417, -3.3077116, 0.23096993], [-1.268438, 2.6097574, -0.3413194], [-0.0041960863, -0.7034186, 1.7076147]]);
+pub fn linear_srgb_to_oklab(rgb: vec3) -> vec3 { LMS_TO_OKLAB * (LINEAR_SRGB_TO_LMS * rgb).map(f32::cbrt) }
+pub fn oklab_to_linear_srgb(lab: vec3) -> vec3 { LMS_TO_LINEAR_SRGB * (OKLAB_TO_LMS * lab).map(|c| c*c*c) }
+
+#[cfg(test)] mod test {
+	use crate::{xyz, xyzw, vec3};
+	use super::*;
+	fn close(a: vec3, b: vec3, e: f32) -> bool { (a-b).iter().all(|d| d.abs() < e) }
+	#[test] fn reference() {
+		assert!((srgb_to_linear(0.5) - 0.214_041_14).abs() < 1e-6); assert!((linear_to_srgb(0.214_041_14) - 0.5).abs() < 1e-6);
+		assert_eq!(srgb_to_linear(0.), 0.); assert!((srgb_to_linear(1.) - 1.).abs() < 1e-6); assert!((linear_to_srgb(1.) - 1.).abs() < 1e-6);
+		let white = xyz{x: 1., y: 1., z: 1.};
+		assert!(close(linear_srgb_to_oklab(white), xyz{x: 1., y: 0., z: 0.}, 1e-4));
+		assert!(close(linear_srgb_to_oklab(xyz{x: 1., y: 0., z: 0.}), xyz{x: 0.627_955, y: 0.224_863, z: 0.125_846}, 1e-4));
+		assert!(close(linear_srgb_to_xyz(white), xyz{x: 0.95047, y: 1., z: 1.08883}, 1e-4));
+		assert_eq!(rgb_to_hsv(xyz{x: 1., y: 0., z: 0.}), xyz{x: 0., y: 1., z: 1.}); assert!(close(rgb_to_hsv(xyz{x: 0., y: 0., z: 1.}), xyz{x: 2./3., y: 1., z: 1.}, 1e-6));
+		assert!(close(rgb_to_hsl(xyz{x: 0., y: 0.5, z: 0.5}), xyz{x: 0.5, y: 1., z: 0.25}, 1e-6));
+		assert_eq!(luminance(white), 1.);
+	}
+	#[test] fn round_trip() {
+		for i in 0..=100 { let c = i as f32 / 100.; assert!((linear_to_srgb(srgb_to_linear(c)) - c).abs() < 1e-5); assert!((srgb_to_linear_fast(c) - srgb_to_linear(c)).abs() < 2e-3 && (linear_to_srgb_fast(c) - linear_to_srgb(c)).abs() < 2e-3); }
+		let grid = (0..6*6*6).map(|i| xyz{x: (i%6) as f32 / 5., y: (i/6%6) as f32 / 5., z: (i/36) as f32 / 5.});
+		for c in grid {
+			assert!(close(hsv_to_rgb(rgb_to_hsv(c)), c, 1e-5), "{c:?}"); assert!(close(hsl_to_rgb(rgb_to_hsl(c)), c, 1e-5), "{c:?}");
+			assert!(close(xyz_to_linear_srgb(linear_srgb_to_xyz(c)), c, 1e-5), "{c:?}"); assert!(close(oklab_to_linear_srgb(linear_srgb_to_oklab(c)), c, 1e-4), "{c:?}");
+			assert!(close(c.srgb_to_linear().linear_to_srgb(), c, 1e-5), "{c:?}");
+		}
+		let c = xyzw{x: 0.5, y: 0.2, z: 0.9, w: 0.3}; assert!((c.srgb_to_linear().linear_to_srgb() - c).iter().all(|d| d.abs() < 1e-5)); assert_eq!(c.srgb_to_linear().w, 0.3);
+		let p = xyzw{x: 1., y: 0.5, z: 0., w: 0.5}.premultiply(); assert_eq!(p, xyzw{x: 0.5, y: 0.25, z: 0., w: 0.5}); assert_eq!(p.unpremultiply(), xyzw{x: 1., y: 0.5, z: 0., w: 0.5});
+	}
+}
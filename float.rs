@@ -0,0 +1,78 @@
+use core::{ops::{Add,Sub,Mul,Div,Neg}, iter::Sum};
+#[cfg(not(any(feature="std", feature="libm")))] use crate::math;
+
+// Float functions for generic code. Rounding, roots, exponentials and trigonometry come from std, or else from libm, or else from pure core approximations (see math.rs for error bounds)
+pub trait Float: Copy+PartialOrd+Sum+From<f32>+Add<Output=Self>+Sub<Output=Self>+Mul<Output=Self>+Div<Output=Self>+Neg<Output=Self> {
+	fn abs(self) -> Self;
+	fn signum(self) -> Self;
+	fn copysign(self, sign: Self) -> Self;
+	fn recip(self) -> Self;
+	fn min(self, b: Self) -> Self;
+	fn max(self, b: Self) -> Self;
+	fn clamp(self, min: Self, max: Self) -> Self;
+	fn is_finite(self) -> bool;
+	fn is_nan(self) -> bool;
+	fn floor(self) -> Self;
+	fn ceil(self) -> Self;
+	// Half away from zero
+	fn round(self) -> Self;
+	fn trunc(self) -> Self;
+	// Same sign as self: self - trunc(self)
+	fn fract(self) -> Self { self - self.trunc() }
+	fn sqrt(self) -> Self;
+	fn cbrt(self) -> Self;
+	fn exp(self) -> Self;
+	fn ln(self) -> Self;
+	fn powf(self, n: Self) -> Self;
+	// self*a+b with a single rounding (except the f64 core fallback)
+	fn mul_add(self, a: Self, b: Self) -> Self;
+	fn sin(self) -> Self;
+	fn cos(self) -> Self;
+	fn sin_cos(self) -> (Self, Self);
+	fn tan(self) -> Self;
+	fn asin(self) -> Self;
+	fn acos(self) -> Self;
+	fn atan(self) -> Self;
+	// Angle of (x=other, y=self)
+	fn atan2(self, other: Self) -> Self;
+}
+
+macro_rules! impl_Float { ($T:ident $sin_cos:ident $fma:ident, $($f:ident $libm:ident)+; $($f2:ident $libm2:ident)+) => {
+impl Float for $T {
+	fn abs(self) -> Self { $T::abs(self) }
+	fn signum(self) -> Self { $T::signum(self) }
+	fn copysign(self, sign: Self) -> Self { $T::copysign(self, sign) }
+	fn recip(self) -> Self { $T::recip(self) }
+	fn min(self, b: Self) -> Self { $T::min(self, b) }
+	fn max(self, b: Self) -> Self { $T::max(self, b) }
+	fn clamp(self, min: Self, max: Self) -> Self { $T::clamp(self, min, max) }
+	fn is_finite(self) -> bool { $T::is_finite(self) }
+	fn is_nan(self) -> bool { $T::is_nan(self) }
+	$(#[cfg(feature="std")] fn $f(self) -> Self { $T::$f(self) })+
+	$(#[cfg(feature="std")] fn $f2(self, b: Self) -> Self { $T::$f2(self, b) })+
+	#[cfg(feature="std")] fn sin_cos(self) -> (Self, Self) { $T::sin_cos(self) }
+	#[cfg(feature="std")] fn mul_add(self, a: Self, b: Self) -> Self { $T::mul_add(self, a, b) }
+	$(#[cfg(all(feature="libm", not(feature="std")))] fn $f(self) -> Self { libm::$libm(self) })+
+	$(#[cfg(all(feature="libm", not(feature="std")))] fn $f2(self, b: Self) -> Self { libm::$libm2(self, b) })+
+	#[cfg(all(feature="libm", not(feature="std")))] fn sin_cos(self) -> (Self, Self) { libm::$sin_cos(self) }
+	#[cfg(all(feature="libm", not(feature="std")))] fn mul_add(self, a: Self, b: Self) -> Self { libm::$fma(self, a, b) }
+	$(#[cfg(not(any(feature="std", feature="libm")))] fn $f(self) -> Self { math::$f(self.into()) as $T })+
+	$(#[cfg(not(any(feature="std", feature="libm")))] fn $f2(self, b: Self) -> Self { math::$f2(self.into(), b.into()) as $T })+
+	#[cfg(not(any(feature="std", feature="libm")))] fn sin_cos(self) -> (Self, Self) { let (s, c) = math::sin_cos(self.into()); (s as $T, c as $T) }
+	#[cfg(not(any(feature="std", feature="libm")))] fn mul_add(self, a: Self, b: Self) -> Self { math::mul_add(self.into(), a.into(), b.into()) as $T }
+}
+} }
+impl_Float!{f32 sincosf fmaf, floor floorf ceil ceilf round roundf trunc truncf sqrt sqrtf cbrt cbrtf exp expf ln logf sin sinf cos cosf tan tanf asin asinf acos acosf atan atanf; powf powf atan2 atan2f}
+impl_Float!{f64 sincos fma, floor floor ceil ceil round round trunc trunc sqrt sqrt cbrt cbrt exp exp ln log sin sin cos cos tan tan asin asin acos acos atan atan; powf pow atan2 atan2}
+
+#[cfg(test)] mod test {
+	use crate::{Float, xy, norm, normalize, distance, rotate, atan};
+	// Through the trait (which must not call itself back with std)
+	fn generic<T:Float>(x: T) -> (T, T, (T, T), T, T) { (x.sqrt(), x.powf(T::from(2.)), x.sin_cos(), x.mul_add(x, T::from(1.)), x.atan2(T::from(1.))) }
+	#[test] fn dispatch() {
+		assert_eq!(norm(xy{x: 3f32, y: 4.}), 5.); assert_eq!(normalize(xy{x: 0f32, y: 2.}), xy{x: 0., y: 1.}); assert_eq!(distance(xy{x: 1f32, y: 1.}, xy{x: 4., y: 5.}), 5.);
+		assert!(norm(rotate(core::f32::consts::FRAC_PI_2, xy{x: 1., y: 0.}) - xy{x: 0., y: 1.}) < 1e-6); assert!((atan(xy{x: 0., y: 1.}) - core::f32::consts::FRAC_PI_2).abs() < 1e-6);
+		let (sqrt, sq, (sin, cos), fma, atan2) = generic(4f32); assert_eq!((sqrt, sq, fma), (2., 16., 17.)); assert!((sin*sin + cos*cos - 1.).abs() < 1e-6 && (atan2 - 4f32.atan2(1.)).abs() < 1e-6);
+		let (sqrt, sq, (sin, cos), fma, _) = generic(0.25f64); assert_eq!((sqrt, sq, fma), (0.5, 0.0625, 1.0625)); assert!((sin - 0.25f64.sin()).abs() < 1e-15 && (cos - 0.25f64.cos()).abs() < 1e-15);
+	}
+}
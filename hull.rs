@@ -0,0 +1,137 @@
+use core::cmp::Ordering::{Equal, Greater};
+use alloc::{vec, vec::Vec, collections::BTreeMap};
+use crate::{xy, vec2, vec3, Orient2d, orient2d, orient3d};
+
+// Andrew's monotone chain: hull vertex indices counterclockwise from the lowest x (then y), without collinear vertices.
+// Duplicates keep the first index. Collinear points yield both ends, a single distinct point yields itself.
+pub fn convex_hull<T:Orient2d+PartialOrd+Copy>(points: &[xy<T>]) -> Vec<u32> {
+	let p = |i: u32| points[i as usize];
+	let mut order: Vec<u32> = (0..points.len() as u32).collect();
+	order.sort_by(|&a, &b| (p(a).x, p(a).y).partial_cmp(&(p(b).x, p(b).y)).unwrap_or(Equal));
+	order.dedup_by(|b, a| p(*a) == p(*b));
+	if order.len() < 3 { return order }
+	let mut hull: Vec<u32> = Vec::with_capacity(order.len()+1);
+	// Lower chain left to right, then upper chain back
+	for &i in &order { while hull.len() >= 2 && T::orient2d(p(hull[hull.len()-2]), p(hull[hull.len()-1]), p(i)) != Greater { hull.pop(); } hull.push(i); }
+	let lower = hull.len()+1;
+	for &i in order.iter().rev().skip(1) { while hull.len() >= lower && T::orient2d(p(hull[hull.len()-2]), p(hull[hull.len()-1]), p(i)) != Greater { hull.pop(); } hull.push(i); }
+	hull.pop(); // Back to the first
+	hull
+}
+
+// Quickhull with exact orientation: triangles counterclockwise seen from outside, coplanar faces are not merged.
+// Coplanar points yield the planar hull fanned on both sides (closed with zero volume). Collinear or coincident points yield none.
+pub fn convex_hull3(points: &[vec3]) -> Vec<[u32; 3]> {
+	let p = |i: u32| points[i as usize];
+	let n = points.len() as u32;
+	if n == 0 { return Vec::new() }
+	// Initial simplex: extreme pair on the longest axis, farthest from their line, farthest from their plane
+	let (_, (a, b)) = [|p: vec3| p.x, |p: vec3| p.y, |p: vec3| p.z].into_iter().map(|c: fn(vec3)->f32| {
+		let min = (0..n).min_by(|&i, &j| c(p(i)).partial_cmp(&c(p(j))).unwrap_or(Equal)).unwrap();
+		let max = (0..n).max_by(|&i, &j| c(p(i)).partial_cmp(&c(p(j))).unwrap_or(Equal)).unwrap();
+		(c(p(max)) - c(p(min)), (min, max))
+	}).max_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap_or(Equal)).unwrap();
+	let projections = |a: vec3, b: vec3, c: vec3| [orient2d(a.xy(), b.xy(), c.xy()), orient2d(a.yz(), b.yz(), c.yz()), orient2d(a.zx(), b.zx(), c.zx())];
+	let Some(c) = (0..n).max_by(|&i, &j| { let d = |i| crate::sq(crate::cross(p(b)-p(a), p(i)-p(a))); d(i).partial_cmp(&d(j)).unwrap_or(Equal) })
+		.filter(|&c| projections(p(a), p(b), p(c)) != [0.; 3]) else { return Vec::new() };
+	let d = (0..n).max_by(|&i, &j| orient3d(p(a), p(b), p(c), p(i)).abs().partial_cmp(&orient3d(p(a), p(b), p(c), p(j)).abs()).unwrap_or(Equal)).unwrap();
+	if orient3d(p(a), p(b), p(c), p(d)) == 0. {
+		// Planar hull in the projection along the dominant axis of the normal
+		let normal = projections(p(a), p(b), p(c)).map(f64::abs);
+		let axis = (0..3).max_by(|&i, &j| normal[i].partial_cmp(&normal[j]).unwrap_or(Equal)).unwrap();
+		let project = |p: vec3| -> vec2 { [p.xy(), p.yz(), p.zx()][axis] };
+		let hull = convex_hull(&points.iter().copied().map(project).collect::<Vec<_>>());
+		return (1..hull.len()-1).flat_map(|i| [[hull[0], hull[i], hull[i+1]], [hull[0], hull[i+1], hull[i]]]).collect();
+	}
+	// Outward: the opposite vertex is below (orient3d > 0), points outside are above (< 0)
+	let outward = |[a, b, c]: [u32; 3], o: u32| if orient3d(p(a), p(b), p(c), p(o)) > 0. { [a, b, c] } else { [a, c, b] };
+	let outside = |[a, b, c]: [u32; 3], i: u32| orient3d(p(a), p(b), p(c), p(i)) < 0.;
+	struct Face { vertices: [u32; 3], outside: Vec<u32>, alive: bool }
+	let mut faces: Vec<Face> = [([a, b, c], d), ([a, b, d], c), ([a, c, d], b), ([b, c, d], a)].into_iter().map(|(f, o)| Face{vertices: outward(f, o), outside: Vec::new(), alive: true}).collect();
+	let mut edges: BTreeMap<(u32, u32), usize> = BTreeMap::new();
+	let edges_of = |[a, b, c]: [u32; 3]| [(a, b), (b, c), (c, a)];
+	for (i, f) in faces.iter().enumerate() { for e in edges_of(f.vertices) { edges.insert(e, i); } }
+	let assign = |faces: &mut [Face], candidates: &[usize], i: u32| if let Some(&f) = candidates.iter().find(|&&f| outside(faces[f].vertices, i)) { faces[f].outside.push(i); };
+	for i in 0..n { if ![a, b, c, d].contains(&i) { assign(&mut faces, &[0, 1, 2, 3], i); } }
+	while let Some(f) = faces.iter().position(|f| f.alive && !f.outside.is_empty()) {
+		let [u, v, w] = faces[f].vertices;
+		let eye = *faces[f].outside.iter().max_by(|&&i, &&j| orient3d(p(u), p(v), p(w), p(j)).partial_cmp(&orient3d(p(u), p(v), p(w), p(i))).unwrap_or(Equal)).unwrap();
+		// Visible faces from the eye, and the horizon edges between visible and hidden faces
+		let (mut visible, mut horizon, mut stack) = (Vec::new(), Vec::new(), vec![f]);
+		faces[f].alive = false;
+		while let Some(f) = stack.pop() {
+			visible.push(f);
+			for (u, v) in edges_of(faces[f].vertices) {
+				let g = edges[&(v, u)];
+				if !faces[g].alive { continue }
+				if outside(faces[g].vertices, eye) { faces[g].alive = false; stack.push(g); } else { horizon.push((u, v)); }
+			}
+		}
+		let orphans: Vec<u32> = visible.iter().flat_map(|&f| core::mem::take(&mut faces[f].outside)).filter(|&i| i != eye).collect();
+		for &f in &visible { for e in edges_of(faces[f].vertices) { edges.remove(&e); } }
+		let new: Vec<usize> = horizon.iter().map(|&(u, v)| {
+			faces.push(Face{vertices: [u, v, eye], outside: Vec::new(), alive: true});
+			for e in edges_of([u, v, eye]) { edges.insert(e, faces.len()-1); }
+			faces.len()-1
+		}).collect();
+		for i in orphans { assign(&mut faces, &new, i); }
+	}
+	faces.iter().filter(|f| f.alive).map(|f| f.vertices).collect()
+}
+
+#[cfg(test)] mod test {
+	use alloc::{vec::Vec, collections::BTreeSet};
+	use crate::{xy, xyz, vec2, vec3, sq, cross, normalize, orient2d, orient3d, Polygon};
+	use super::{convex_hull, convex_hull3};
+	fn p(x: f32, y: f32) -> vec2 { xy{x, y} }
+	fn v(x: f32, y: f32, z: f32) -> vec3 { xyz{x, y, z} }
+	#[test] fn planar() {
+		// Collinear and duplicate
+		let points = [p(0., 0.), p(1., 1.), p(2., 0.), p(2., 2.), p(0., 2.), p(1., 0.), p(0., 1.), p(1., 1.), p(2., 2.)];
+		assert_eq!(convex_hull(&points), [0, 2, 3, 4]);
+		assert!(convex_hull::<f32>(&[]).is_empty()); assert_eq!(convex_hull(&[p(1., 1.), p(1., 1.)]), [0]);
+		assert_eq!(convex_hull(&[p(0., 0.), p(3., 3.), p(1., 1.), p(2., 2.)]), [0, 1]); assert_eq!(convex_hull(&[p(2., 2.), p(0., 0.), p(2., 2.), p(1., 1.)]), [1, 0]);
+		let i = |x, y| xy{x, y};
+		assert_eq!(convex_hull(&[i(i32::MIN, i32::MIN), i(i32::MAX, i32::MIN), i(0, 0), i(i32::MAX, i32::MAX), i(i32::MIN, i32::MAX), i(i32::MAX, 0)]), [0, 1, 3, 4]);
+		// Nearly collinear: strictly convex with the exact predicate
+		let points: Vec<vec2> = (0..100).map(|k| { let x = 0.5 + k as f32 * f32::EPSILON; p(x, x + if k%3 == 0 { f32::EPSILON } else { 0. }) }).chain([p(0., 0.), p(1., 1.)]).collect();
+		let hull: Vec<vec2> = convex_hull(&points).iter().map(|&i| points[i as usize]).collect();
+		let n = hull.len();
+		for k in 0..n { assert!(orient2d(hull[k], hull[(k+1)%n], hull[(k+2)%n]) > 0.); for &q in &points { assert!(orient2d(hull[k], hull[(k+1)%n], q) >= 0.); } }
+		assert!(Polygon(&hull).is_convex());
+	}
+	// Nondegenerate faces with every point inside or on
+	fn check_planes(points: &[vec3], faces: &[[u32; 3]]) {
+		let p = |i: u32| points[i as usize];
+		for &[a, b, c] in faces { assert!(sq(cross(p(b)-p(a), p(c)-p(a))) > 0.); for &q in points { assert!(orient3d(p(a), p(b), p(c), q) >= 0., "{a} {b} {c} {q:?}"); } }
+	}
+	// Closed: each directed edge once, with its opposite, and of genus 0
+	fn check(points: &[vec3], faces: &[[u32; 3]]) {
+		check_planes(points, faces);
+		let edges: BTreeSet<(u32, u32)> = faces.iter().flat_map(|&[a, b, c]| [(a, b), (b, c), (c, a)]).collect();
+		assert_eq!(edges.len(), 3*faces.len()); for &(a, b) in &edges { assert!(edges.contains(&(b, a))); }
+		let vertices = faces.iter().flatten().collect::<BTreeSet<_>>().len();
+		assert_eq!(vertices + faces.len(), edges.len()/2 + 2);
+	}
+	#[test] fn spatial() {
+		let cube: Vec<vec3> = (0..8).map(|i| v((i&1) as f32, ((i>>1)&1) as f32, ((i>>2)&1) as f32)).chain([v(0.5, 0.5, 0.5), v(0.5, 0.5, 0.), v(0.2, 0.3, 0.9), v(1., 1., 1.)]).collect();
+		let faces = convex_hull3(&cube); check(&cube, &faces); assert_eq!(faces.len(), 12); assert!(faces.iter().flatten().all(|&i| i < 8 || cube[i as usize] == cube[7]));
+		let tetra = [v(0., 0., 0.), v(1., 0., 0.), v(0., 1., 0.), v(0., 0., 1.)];
+		let faces = convex_hull3(&tetra); check(&tetra, &faces); assert_eq!(faces.len(), 4);
+		// Degenerate
+		assert!(convex_hull3(&[]).is_empty()); assert!(convex_hull3(&[v(1., 2., 3.); 5]).is_empty());
+		assert!(convex_hull3(&[v(0., 0., 0.), v(1., 1., 1.), v(2., 2., 2.), v(3., 3., 3.), v(1., 1., 1.)]).is_empty());
+		// Coplanar: both sides of the fan, whichever the plane
+		let square = [v(0., 0., 1.), v(1., 0., 1.), v(1., 1., 1.), v(0., 1., 1.), v(0.5, 0.5, 1.), v(0.5, 0., 1.)];
+		for plane in [square.map(|q| q), square.map(|q| v(q.x, q.z, q.y)), square.map(|q| v(q.z, q.x, q.y)), square.map(|q| v(q.x, q.y, q.x + 2.*q.y))] {
+			let faces = convex_hull3(&plane); assert_eq!(faces.len(), 4); check_planes(&plane, &faces);
+		}
+		// Random clouds, and grids with many coplanar points
+		let mut seed = 7u64; let mut rand = move || { seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407); (seed >> 40) as f32 / (1u64 << 24) as f32 };
+		for _ in 0..20 {
+			let cloud: Vec<vec3> = (0..200).map(|_| v(rand()-0.5, rand()-0.5, rand()-0.5)).collect(); check(&cloud, &convex_hull3(&cloud));
+			let sphere: Vec<vec3> = (0..200).map(|_| (0.9 + 0.1*rand()) * normalize(v(rand()-0.5, rand()-0.5, rand()-0.5))).collect(); check(&sphere, &convex_hull3(&sphere));
+			let grid: Vec<vec3> = (0..100).map(|_| v((rand()*4.) as u32 as f32, (rand()*4.) as u32 as f32, (rand()*4.) as u32 as f32)).collect(); check(&grid, &convex_hull3(&grid));
+		}
+	}
+}
@@ -0,0 +1,90 @@
+use core::ops::{Deref, DerefMut, Index, IndexMut};
+use alloc::vec::Vec;
+use crate::{xy, uint2, int2, size, Rect};
+
+// Row-major 2D grid over any slice storage (Vec<T>, &[T], &mut [T]). stride: elements between rows (>= size.x)
+#[derive(Clone, Debug, PartialEq, Eq)] pub struct Image<D> { pub data: D, pub size: size, pub stride: u32 }
+
+impl<D> Image<D> {
+	pub fn bounds(&self) -> Rect { self.size.into() }
+	pub fn offset(&self, xy{x,y}: uint2) -> usize { y as usize*self.stride as usize+x as usize }
+	pub fn contains(&self, p: uint2) -> bool { p.x < self.size.x && p.y < self.size.y }
+	// Elements from the first to the last pixel (a view's data extends to the end of its parent's)
+	fn span(&self) -> usize { if self.size.x == 0 || self.size.y == 0 { 0 } else { (self.size.y-1) as usize*self.stride as usize+self.size.x as usize } }
+}
+
+impl<T, D:Deref<Target=[T]>> Image<D> {
+	#[track_caller] pub fn new(size: size, data: D) -> Self { let image = Self{data, size, stride: size.x}; assert!(image.data.len() >= image.span()); image }
+	pub fn get(&self, p: uint2) -> Option<&T> { self.contains(p).then(|| &self.data[self.offset(p)]) }
+	#[track_caller] pub fn row(&self, y: u32) -> &[T] { assert!(y < self.size.y); &self.data[self.offset(xy{x: 0, y})..][..self.size.x as usize] }
+	pub fn rows<'t>(&'t self) -> impl DoubleEndedIterator<Item=&'t [T]> + ExactSizeIterator where T:'t { (0..self.size.y).map(|y| self.row(y)) }
+	pub fn iter<'t>(&'t self) -> impl Iterator<Item=&'t T> where T:'t { self.rows().flatten() }
+	pub fn as_ref(&self) -> Image<&[T]> { Image{data: &self.data, size: self.size, stride: self.stride} }
+	// Clipped to the image
+	pub fn view(&self, rect: Rect) -> Image<&[T]> {
+		let rect = self.bounds().clip(rect).unsigned();
+		Image{data: &self.data[self.offset(rect.min).min(self.data.len())..], size: rect.size(), stride: self.stride}
+	}
+	pub fn map<U>(&self, f: impl FnMut(&T)->U) -> Image<Vec<U>> { Image::new(self.size, self.iter().map(f).collect()) }
+	#[track_caller] pub fn zip<U, E:Deref<Target=[U]>, V>(&self, b: &Image<E>, mut f: impl FnMut(&T, &U)->V) -> Image<Vec<V>> {
+		assert_eq!(self.size, b.size);
+		Image::new(self.size, self.iter().zip(b.iter()).map(|(a,b)| f(a,b)).collect())
+	}
+}
+// Rows including the stride padding in between (i.e for a view, the parent pixels beside it)
+impl<T:bytemuck::Pod, D:Deref<Target=[T]>> Image<D> { pub fn as_bytes(&self) -> &[u8] { bytemuck::cast_slice(&self.data[..self.span()]) } }
+
+impl<T, D:DerefMut<Target=[T]>> Image<D> {
+	pub fn get_mut(&mut self, p: uint2) -> Option<&mut T> { self.contains(p).then(|| { let i = self.offset(p); &mut self.data[i] }) }
+	#[track_caller] pub fn row_mut(&mut self, y: u32) -> &mut [T] { assert!(y < self.size.y); let i = self.offset(xy{x: 0, y}); &mut self.data[i..][..self.size.x as usize] }
+	pub fn as_mut(&mut self) -> Image<&mut [T]> { Image{data: &mut self.data, size: self.size, stride: self.stride} }
+	pub fn view_mut(&mut self, rect: Rect) -> Image<&mut [T]> {
+		let rect = self.bounds().clip(rect).unsigned();
+		let i = self.offset(rect.min).min(self.data.len());
+		Image{data: &mut self.data[i..], size: rect.size(), stride: self.stride}
+	}
+	pub fn fill(&mut self, value: T) where T:Clone { for y in 0..self.size.y { self.row_mut(y).fill(value.clone()); } }
+	pub fn set(&mut self, mut f: impl FnMut(uint2)->T) { for y in 0..self.size.y { for (x, e) in self.row_mut(y).iter_mut().enumerate() { *e = f(xy{x: x as u32, y}); } } }
+	// Copies the overlapping top-left region
+	pub fn copy_from<E:Deref<Target=[T]>>(&mut self, source: &Image<E>) where T:Copy {
+		let xy{x: width, y: height} = crate::component_wise_min(self.size, source.size);
+		for y in 0..height { self.row_mut(y)[..width as usize].copy_from_slice(&source.row(y)[..width as usize]); }
+	}
+	// Copies rect of source at target (both clipped)
+	pub fn copy_rect_from<E:Deref<Target=[T]>>(&mut self, target: int2, source: &Image<E>, rect: Rect) where T:Copy {
+		let rect = source.bounds().clip(rect);
+		let target = Rect{min: target, max: target+rect.size()};
+		let clipped = self.bounds().clip(target);
+		self.view_mut(clipped).copy_from(&source.view((rect.min - target.min) + clipped));
+	}
+}
+impl<T:bytemuck::Pod, D:DerefMut<Target=[T]>> Image<D> { pub fn as_bytes_mut(&mut self) -> &mut [u8] { let span = self.span(); bytemuck::cast_slice_mut(&mut self.data[..span]) } }
+
+impl<T> Image<Vec<T>> {
+	pub fn from_fn(size: size, f: impl FnMut(uint2)->T) -> Self { Self::new(size, (0..size.y).flat_map(|y| (0..size.x).map(move |x| xy{x,y})).map(f).collect()) }
+	pub fn from_value(size: size, value: T) -> Self where T:Clone { Self::new(size, alloc::vec![value; size.x as usize*size.y as usize]) }
+	pub fn zero(size: size) -> Self where T:num::Zero+Clone { Self::from_value(size, T::ZERO) }
+}
+
+impl<T, D:Deref<Target=[T]>> Index<uint2> for Image<D> { type Output = T; #[track_caller] fn index(&self, p: uint2) -> &T { self.get(p).unwrap() } }
+impl<T, D:DerefMut<Target=[T]>> IndexMut<uint2> for Image<D> { #[track_caller] fn index_mut(&mut self, p: uint2) -> &mut T { self.get_mut(p).unwrap() } }
+
+#[cfg(test)] mod test {
+	use crate::{xy, Rect};
+	use super::Image;
+	#[test] fn view_bytes() {
+		let mut image = Image::from_fn(xy{x: 4, y: 3}, |xy{x,y}| (y*4+x) as u8);
+		assert_eq!(image.as_bytes(), &(0..12).collect::<alloc::vec::Vec<u8>>());
+		let rect = Rect{min: xy{x: 1, y: 1}, max: xy{x: 3, y: 3}};
+		assert_eq!(image.view(rect).as_bytes(), [5, 6, 7, 8, 9, 10]);
+		assert!(image.view(Rect{min: xy{x: 4, y: 0}, max: xy{x: 4, y: 3}}).as_bytes().is_empty());
+		image.view_mut(rect).as_bytes_mut().fill(0);
+		assert_eq!(image.as_bytes()[11], 11); assert_eq!(image.data[..5], [0, 1, 2, 3, 4]);
+	}
+	#[test] fn large_offsets() {
+		let image = Image{data: &[0u8][..], size: xy{x: 1 << 20, y: 1 << 20}, stride: 1 << 20};
+		assert_eq!(image.offset(xy{x: 1, y: (1 << 20) - 1}), (((1usize << 20) - 1) << 20) + 1); assert_eq!(image.span(), 1 << 40);
+		assert!(Image::new(xy{x: 0, y: 1 << 31}, &[0u8; 0][..]).as_bytes().is_empty());
+	}
+	#[test] #[should_panic] fn short_data() { Image::new(xy{x: 4, y: 3}, &[0u8; 11][..]); }
+}
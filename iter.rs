@@ -0,0 +1,92 @@
+use core::ops::Range;
+use crate::{xy, MinMax, component_wise_min};
+
+// Rows of a rectangle as (y, x range)
+#[derive(Clone, Debug)] pub struct Rows<T> { x: MinMax<T>, y: Range<T> }
+impl<T: Copy> Iterator for Rows<T> where Range<T>: Iterator<Item=T> {
+	type Item = (T, Range<T>);
+	fn next(&mut self) -> Option<Self::Item> { self.y.next().map(|y| (y, self.x.into())) }
+	fn size_hint(&self) -> (usize, Option<usize>) { self.y.size_hint() }
+}
+impl<T: Copy> DoubleEndedIterator for Rows<T> where Range<T>: DoubleEndedIterator<Item=T> { fn next_back(&mut self) -> Option<Self::Item> { self.y.next_back().map(|y| (y, self.x.into())) } }
+impl<T: Copy> ExactSizeIterator for Rows<T> where Range<T>: ExactSizeIterator<Item=T> {}
+impl<T: Copy> core::iter::FusedIterator for Rows<T> where Range<T>: core::iter::FusedIterator<Item=T> {}
+
+// Points of a rectangle in row-major order
+#[derive(Clone, Debug)] pub struct Points<T> { min: xy<T>, width: usize, index: Range<usize> }
+// Tiles of a rectangle in row-major order, clipped to the rectangle on the last row and column
+#[derive(Clone, Debug)] pub struct Tiles<T> { rect: MinMax<xy<T>>, size: xy<T>, columns: usize, index: Range<usize> }
+
+macro_rules! impl_iterators { ($($T:ident)+) => {$(
+impl MinMax<xy<$T>> {
+	pub fn rows(self) -> Rows<$T> { Rows{x: MinMax{min: self.min.x, max: self.max.x}, y: if self.is_empty() { self.min.y..self.min.y } else { self.min.y..self.max.y }} }
+	pub fn points(self) -> Points<$T> {
+		let xy{x,y} = if self.is_empty() { xy{x: 0, y: 0} } else { self.extent() };
+		Points{min: self.min, width: x, index: 0..x*y}
+	}
+	// size() without overflow for rectangles wider than $T::MAX
+	fn extent(self) -> xy<usize> { xy{x: self.max.x.abs_diff(self.min.x) as usize, y: self.max.y.abs_diff(self.min.y) as usize} }
+	#[track_caller] pub fn tiles(self, size: xy<$T>) -> Tiles<$T> {
+		assert!(size.x > 0 && size.y > 0);
+		let xy{x,y} = if self.is_empty() { xy{x: 0, y: 0} } else { self.extent().zip(size).map(|(n, d)| n.div_ceil(d as usize)).collect() };
+		Tiles{rect: self, size, columns: x, index: 0..x*y}
+	}
+}
+// Offsets are less than the extent so the wrapping sums land back inside the rectangle
+impl Points<$T> { fn point(&self, i: usize) -> xy<$T> { xy{x: self.min.x.wrapping_add((i % self.width) as $T), y: self.min.y.wrapping_add((i / self.width) as $T)} } }
+impl Iterator for Points<$T> {
+	type Item = xy<$T>;
+	fn next(&mut self) -> Option<Self::Item> { self.index.next().map(|i| self.point(i)) }
+	fn size_hint(&self) -> (usize, Option<usize>) { self.index.size_hint() }
+	fn nth(&mut self, n: usize) -> Option<Self::Item> { self.index.nth(n).map(|i| self.point(i)) }
+}
+impl DoubleEndedIterator for Points<$T> { fn next_back(&mut self) -> Option<Self::Item> { self.index.next_back().map(|i| self.point(i)) } }
+impl ExactSizeIterator for Points<$T> {}
+impl core::iter::FusedIterator for Points<$T> {}
+impl Tiles<$T> { fn tile(&self, i: usize) -> MinMax<xy<$T>> {
+	let min = xy{x: self.rect.min.x.wrapping_add(((i % self.columns) * self.size.x as usize) as $T), y: self.rect.min.y.wrapping_add(((i / self.columns) * self.size.y as usize) as $T)};
+	MinMax{min, max: component_wise_min(xy{x: min.x.saturating_add(self.size.x), y: min.y.saturating_add(self.size.y)}, self.rect.max)}
+} }
+impl Iterator for Tiles<$T> {
+	type Item = MinMax<xy<$T>>;
+	fn next(&mut self) -> Option<Self::Item> { self.index.next().map(|i| self.tile(i)) }
+	fn size_hint(&self) -> (usize, Option<usize>) { self.index.size_hint() }
+	fn nth(&mut self, n: usize) -> Option<Self::Item> { self.index.nth(n).map(|i| self.tile(i)) }
+}
+impl DoubleEndedIterator for Tiles<$T> { fn next_back(&mut self) -> Option<Self::Item> { self.index.next_back().map(|i| self.tile(i)) } }
+impl ExactSizeIterator for Tiles<$T> {}
+impl core::iter::FusedIterator for Tiles<$T> {}
+)+} }
+impl_iterators!{u32 i32}
+
+#[cfg(test)] mod test {
+	use crate::{xy, uint2, MinMax, Rect};
+	fn r(x0: i32, y0: i32, x1: i32, y1: i32) -> Rect { MinMax{min: xy{x: x0, y: y0}, max: xy{x: x1, y: y1}} }
+	fn u(x0: u32, y0: u32, x1: u32, y1: u32) -> MinMax<uint2> { MinMax{min: xy{x: x0, y: y0}, max: xy{x: x1, y: y1}} }
+	#[test] fn points() {
+		let rect = r(-1, 2, 2, 4); let points = [(-1, 2), (0, 2), (1, 2), (-1, 3), (0, 3), (1, 3)].map(|(x, y)| xy{x, y});
+		assert!(rect.points().eq(points)); assert!(rect.points().rev().eq(points.into_iter().rev()));
+		assert_eq!(rect.points().len(), 6); assert_eq!(rect.points().nth(4), Some(xy{x: 0, y: 3})); assert_eq!(rect.points().next_back(), Some(xy{x: 1, y: 3}));
+		let mut iter = rect.points(); iter.next(); iter.next_back(); assert_eq!(iter.len(), 4); assert!(iter.eq(points[1..5].iter().copied()));
+		assert!(rect.rows().eq([(2, -1..2), (3, -1..2)])); assert!(rect.rows().rev().eq([(3, -1..2), (2, -1..2)])); assert_eq!(rect.rows().len(), 2);
+	}
+	#[test] fn tiles() {
+		let rect = u(0, 0, 5, 3); let tiles = [u(0, 0, 2, 2), u(2, 0, 4, 2), u(4, 0, 5, 2), u(0, 2, 2, 3), u(2, 2, 4, 3), u(4, 2, 5, 3)]; // Partial last column and row
+		assert!(rect.tiles(xy{x: 2, y: 2}).eq(tiles)); assert!(rect.tiles(xy{x: 2, y: 2}).rev().eq(tiles.into_iter().rev())); assert_eq!(rect.tiles(xy{x: 2, y: 2}).len(), 6);
+		assert_eq!(rect.tiles(xy{x: 2, y: 2}).map(|t| t.area()).sum::<u32>(), 15); assert!(rect.tiles(xy{x: 8, y: 8}).eq([rect])); assert_eq!(rect.tiles(xy{x: 1, y: 1}).len(), 15);
+		assert!(r(-3, -1, 1, 1).tiles(xy{x: 3, y: 2}).eq([r(-3, -1, 0, 1), r(0, -1, 1, 1)])); assert_eq!(r(-3, -1, 1, 1).tiles(xy{x: 3, y: 2}).nth(1), Some(r(0, -1, 1, 1)));
+	}
+	#[test] fn empty() {
+		for rect in [r(3, 0, 1, 5), r(0, 0, 0, 5), r(0, 5, 4, 5), num::zero()] {
+			assert_eq!(rect.points().len(), 0); assert_eq!(rect.points().next(), None); assert_eq!(rect.points().next_back(), None);
+			assert_eq!(rect.rows().len(), 0); assert_eq!(rect.tiles(xy{x: 1, y: 1}).len(), 0); assert_eq!(rect.tiles(xy{x: 1, y: 1}).next_back(), None);
+		}
+	}
+	#[test] fn extremes() {
+		let max = i32::MAX; let rect = r(max-3, max-1, max, max);
+		assert!(rect.points().eq([xy{x: max-3, y: max-1}, xy{x: max-2, y: max-1}, xy{x: max-1, y: max-1}])); assert!(rect.tiles(xy{x: 2, y: 2}).eq([r(max-3, max-1, max-1, max), r(max-1, max-1, max, max)]));
+		let wide = r(i32::MIN, 0, max, 1); assert_eq!(wide.points().len(), u32::MAX as usize); assert_eq!(wide.points().next_back(), Some(xy{x: max-1, y: 0})); assert_eq!(wide.points().nth(1 << 31), Some(xy{x: 0, y: 0}));
+		assert!(wide.tiles(xy{x: max, y: 1}).eq([r(i32::MIN, 0, -1, 1), r(-1, 0, max-1, 1), r(max-1, 0, max, 1)]));
+		let max = u32::MAX; assert!(u(0, max-1, max, max).tiles(xy{x: 1 << 31, y: 2}).eq([u(0, max-1, 1 << 31, max), u(1 << 31, max-1, max, max)])); assert!(u(max-2, 0, max, 1).points().eq([xy{x: max-2, y: 0}, xy{x: max-1, y: 0}]));
+	}
+}
@@ -1,19 +1,28 @@
 #![no_std]
-#![allow(incomplete_features)]#![cfg_attr(feature="generic_const_exprs",feature(generic_const_exprs))] // matrix
+#[cfg(feature="std")] extern crate std;
+#[cfg(feature="alloc")] extern crate alloc;
 use core::{ops::{Mul,Div,Sub}, iter::Sum};
 
 pub fn dot<T:Mul>(a: T, b: T) -> <T::Output as IntoIterator>::Item where T::Output: IntoIterator<Item: Sum> { (a*b).into_iter().sum() }
 pub fn sq<T:Mul+Copy>(v: T) -> <T::Output as IntoIterator>::Item where T::Output: IntoIterator<Item: Sum> { dot(v, v) }
-pub fn norm<T:Mul+Copy>(v: T) -> <T::Output as IntoIterator>::Item where T::Output: IntoIterator<Item: Sum+num::Sqrt> { num::Sqrt::sqrt(sq(v)) }
+pub fn norm<T:Mul+Copy>(v: T) -> <T::Output as IntoIterator>::Item where T::Output: IntoIterator<Item: Sum+Float> { Float::sqrt(sq(v)) }
 
 pub fn normalize<T:Mul+Copy+ Div<<<T as Mul>::Output as IntoIterator>::Item> >(v: T) -> <T as Div<<<T as Mul>::Output as IntoIterator>::Item> >::Output
-  where <T as Mul>::Output: IntoIterator<Item: Sum+num::Sqrt>
+  where <T as Mul>::Output: IntoIterator<Item: Sum+Float>
 	{ v/norm(v) }
 
 pub fn distance<T:Sub>(a: T, b: T) -> <<<T as Sub>::Output as Mul>::Output as IntoIterator>::Item
-	where T::Output: Mul+Copy, <<T as Sub>::Output as Mul>::Output: IntoIterator<Item: Sum+num::Sqrt>
+	where T::Output: Mul+Copy, <<T as Sub>::Output as Mul>::Output: IntoIterator<Item: Sum+Float>
 	{ norm(b-a) }
 
+// Scales by the largest magnitude so that squares neither overflow nor underflow (as hypot). Slower than norm
+pub fn robust_norm<T:Float>(v: impl IntoIterator<Item=T>+Copy) -> T {
+	let scale = v.into_iter().fold(T::from(0.), |m, c| m.max(c.abs()));
+	if scale == T::from(0.) || !scale.is_finite() { return scale }
+	scale * v.into_iter().map(|c| { let c = c/scale; c*c }).sum::<T>().sqrt()
+}
+pub fn robust_normalize<V:IntoIterator<Item=T>+Copy+Div<T>, T:Float>(v: V) -> V::Output { v/robust_norm(v) }
+
 // Yields min/max of each components. By comparison, std::cmp::{min,max}(impl Ord) yields either value completely.
 pub trait ComponentWiseMinMax {
 	fn component_wise_min(self, other: Self) -> Self;
@@ -32,10 +41,68 @@ macro_rules! impl_ComponentWiseMinMax {
 	)+};
 }
 impl_ComponentWiseMinMax!{u8 i8 u16 i16 u32 i32 f32 u64 i64 f64}
+
+// Whether each component is less. By comparison, vectors PartialOrd is not a product order
+pub trait ComponentWiseLess { fn component_wise_lt(&self, other: &Self) -> bool; }
+macro_rules! impl_ComponentWiseLess { ($($T:ident)+) => {$( impl ComponentWiseLess for $T { fn component_wise_lt(&self, other: &Self) -> bool { self < other } } )+}; }
+impl_ComponentWiseLess!{u8 i8 u16 i16 u32 i32 f32 u64 i64 f64}
+
+// Conversions between primitives: try_cast is None when out of range (or NaN), truncating floats. saturating_cast clamps (NaN to 0) as `as` does for floats.
+pub trait Cast<U> { fn try_cast(self) -> Option<U>; fn saturating_cast(self) -> U; }
+// Float to integer conversions with explicit rounding, saturating as saturating_cast
+pub trait RoundTo<I> { fn round_to(self) -> I; fn floor_to(self) -> I; fn ceil_to(self) -> I; fn trunc_to(self) -> I; }
+macro_rules! impl_Cast {
+	($I:tt $F:tt) => { impl_Cast!{ints $I $I $F} impl_Cast!{floats $F $I $F} };
+	(ints [$($I:ident)+] $J:tt $F:tt) => { $(impl_Cast!{int $I $J $F})+ };
+	(floats [$($F:ident)+] $I:tt $G:tt) => { $(impl_Cast!{float $F $I $G})+ };
+	(int $I:ident [$($J:ident)+] [$($F:ident)+]) => {
+		$(impl Cast<$J> for $I {
+			fn try_cast(self) -> Option<$J> { self.try_into().ok() }
+			fn saturating_cast(self) -> $J { self.try_into().unwrap_or(if self > 0 { $J::MAX } else { $J::MIN }) }
+		})+
+		$(impl Cast<$F> for $I { fn try_cast(self) -> Option<$F> { Some(self as $F) } fn saturating_cast(self) -> $F { self as $F } })+
+	};
+	(float $F:ident [$($I:ident)+] [$($G:ident)+]) => {
+		$(impl Cast<$I> for $F {
+			// self-MIN is exact near MIN (Sterbenz), MAX+1 rounds to the next power of two
+			fn try_cast(self) -> Option<$I> { (self - $I::MIN as $F > -1. && self < $I::MAX as $F + 1.).then_some(self as $I) }
+			fn saturating_cast(self) -> $I { self as $I }
+		}
+		impl RoundTo<$I> for $F {
+			fn trunc_to(self) -> $I { self as $I }
+			// Integer part and fraction are exact
+			fn floor_to(self) -> $I { let t = self as $I; if (t as $F) > self { t.saturating_sub(1) } else { t } }
+			fn ceil_to(self) -> $I { let t = self as $I; if (t as $F) < self { t.saturating_add(1) } else { t } }
+			// Half away from zero
+			fn round_to(self) -> $I { let t = self as $I; let f = self - t as $F; if f >= 0.5 { t.saturating_add(1) } else if f <= -0.5 { t.saturating_sub(1) } else { t } }
+		})+
+		$(impl Cast<$G> for $F {
+			fn try_cast(self) -> Option<$G> { let g = self as $G; (!self.is_nan() && g.is_finite() == self.is_finite()).then_some(g) }
+			fn saturating_cast(self) -> $G { if self.is_finite() { (self as $G).clamp($G::MIN, $G::MAX) } else { self as $G } }
+		})+
+	};
+}
+impl_Cast!{[u8 i8 u16 i16 u32 i32 u64 i64] [f32 f64]}
+
+// Fixed point [0,1] (unorm: u8, u16) or [-1,1] (snorm: i8, i16) as stored in textures and vertex buffers. Encoding clamps and rounds to nearest (NaN to 0)
+pub trait Normalized: Sized { fn encode(v: f32) -> Self; fn decode(self) -> f32; }
+macro_rules! impl_Normalized {
+	(unorm $($T:ident)+) => {$( impl Normalized for $T {
+		fn encode(v: f32) -> Self { RoundTo::round_to(v.clamp(0., 1.) * $T::MAX as f32) }
+		fn decode(self) -> f32 { self as f32 / $T::MAX as f32 }
+	} )+};
+	// -MAX-1 also decodes to -1
+	(snorm $($T:ident)+) => {$( impl Normalized for $T {
+		fn encode(v: f32) -> Self { RoundTo::round_to(v.clamp(-1., 1.) * $T::MAX as f32) }
+		fn decode(self) -> f32 { (self as f32 / $T::MAX as f32).max(-1.) }
+	} )+};
+}
+impl_Normalized!{unorm u8 u16}
+impl_Normalized!{snorm i8 i16}
 pub fn min<T: ComponentWiseMinMax+Copy>(iter: impl IntoIterator<Item=T>) -> Option<T> { iter.into_iter().reduce(ComponentWiseMinMax::component_wise_min) }
 pub fn max<T: ComponentWiseMinMax+Copy>(iter: impl IntoIterator<Item=T>) -> Option<T> { iter.into_iter().reduce(ComponentWiseMinMax::component_wise_max) }
 
-#[derive(PartialEq,Eq,Clone,Copy,Debug)] pub struct MinMax<T> { pub min: T, pub max: T }
+#[derive(PartialEq,Eq,Clone,Copy,Debug)] #[cfg_attr(feature="serde", derive(serde::Serialize, serde::Deserialize))] pub struct MinMax<T> { pub min: T, pub max: T }
 impl<T:core::fmt::Display> core::fmt::Display for MinMax<T> {  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result { write!(f, "{}x{}", self.min, self.max) } }
 impl<T:num::Zero> num::Zero for MinMax<T> { const ZERO: Self = MinMax{min: T::ZERO, max: T::ZERO}; }
 impl<T> From<MinMax<T>> for core::ops::Range<T> { fn from(MinMax{min,max}: MinMax<T>) -> Self { min .. max }}
@@ -53,6 +120,15 @@ impl<T:ComponentWiseMinMax+Copy> MinMax<T> {
 impl<T:ComponentWiseMinMax+Copy+PartialEq> MinMax<T> {
 	pub fn contains(&self, p: T) -> bool { component_wise_min(self.min, p) == self.min && component_wise_max(self.max, p) == self.max }
 }
+impl<T:ComponentWiseMinMax+ComponentWiseLess+Copy> MinMax<T> {
+	// Half open: empty unless min < max for every component
+	pub fn is_empty(&self) -> bool { !self.min.component_wise_lt(&self.max) }
+	pub fn intersection(self, b: Self) -> Option<Self> { let r = Self{min: component_wise_max(self.min, b.min), max: component_wise_min(self.max, b.max)}; (!r.is_empty()).then_some(r) }
+	pub fn intersects(self, b: Self) -> bool { self.intersection(b).is_some() }
+}
+impl<T:ComponentWiseMinMax+ComponentWiseLess+Copy+PartialEq> MinMax<T> {
+	pub fn contains_rect(&self, b: &Self) -> bool { b.is_empty() || (component_wise_min(self.min, b.min) == self.min && component_wise_max(self.max, b.max) == self.max) }
+}
 impl<T:core::ops::AddAssign+Copy> MinMax<T> { pub fn translate(&mut self, offset: T) { self.min += offset; self.max += offset; } }
 impl<T:core::ops::Sub> MinMax<T> { pub fn size(self) -> T::Output { self.max-self.min } }
 impl<T> MinMax<T> {
@@ -77,23 +153,78 @@ pub extern crate num;
 pub extern crate bytemuck;
 #[cfg(feature="serde")] pub extern crate serde;
 
+// Defined here rather than as cfg_attr within vector! so that the feature is evaluated for this crate and not for the crate invoking vector!
+#[cfg(feature="serde")] #[doc(hidden)] #[macro_export] macro_rules! impl_serde { ($N:literal $Vector:ident $($c:ident)+) => {
+impl<T:$crate::serde::Serialize> $crate::serde::Serialize for $Vector<T> { fn serialize<S:$crate::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+	use $crate::serde::ser::SerializeStruct;
+	let mut s = serializer.serialize_struct(stringify!($Vector), $N)?;
+	$(s.serialize_field(stringify!($c), &self.$c)?;)+
+	s.end()
+} }
+impl<'de> $crate::serde::Deserialize<'de> for Component { fn deserialize<D:$crate::serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+	struct Visitor;
+	impl<'de> $crate::serde::de::Visitor<'de> for Visitor {
+		type Value = Component;
+		fn expecting(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result { write!(f, "one of {:?}", [$(stringify!($c)),+]) }
+		fn visit_u64<E:$crate::serde::de::Error>(self, i: u64) -> Result<Component, E> { Component::enumerate().get(i as usize).copied().ok_or_else(|| E::invalid_value($crate::serde::de::Unexpected::Unsigned(i), &self)) }
+		fn visit_str<E:$crate::serde::de::Error>(self, name: &str) -> Result<Component, E> {
+			[$(stringify!($c)),+].iter().position(|&c| c == name).map(|i| Component::enumerate()[i]).ok_or_else(|| E::unknown_field(name, &[$(stringify!($c)),+]))
+		}
+	}
+	deserializer.deserialize_identifier(Visitor)
+} }
+impl<'de, T:$crate::serde::Deserialize<'de>> $crate::serde::Deserialize<'de> for $Vector<T> { fn deserialize<D:$crate::serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+	struct Visitor<T>(core::marker::PhantomData<T>);
+	impl<'de, T:$crate::serde::Deserialize<'de>> $crate::serde::de::Visitor<'de> for Visitor<T> {
+		type Value = $Vector<T>;
+		fn expecting(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result { write!(f, "{} {{{}}} or [{}]", stringify!($Vector), stringify!($($c),+), stringify!($($c),+)) }
+		fn visit_seq<A:$crate::serde::de::SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+			let mut len = 0;
+			let v = $Vector{$($c: { let c = seq.next_element()?.ok_or_else(|| $crate::serde::de::Error::invalid_length(len, &self))?; len += 1; c }),+};
+			if seq.next_element::<$crate::serde::de::IgnoredAny>()?.is_some() { return Err($crate::serde::de::Error::invalid_length(len+1, &self)); }
+			Ok(v)
+		}
+		fn visit_map<A:$crate::serde::de::MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+			let mut v : [Option<T>; $N] = core::array::from_fn(|_| None);
+			while let Some(c) = map.next_key::<Component>()? {
+				let c = &mut v[c as usize];
+				if c.is_some() { return Err($crate::serde::de::Error::custom("duplicate component")); }
+				*c = Some(map.next_value()?);
+			}
+			let [$($c),+] = v;
+			Ok($Vector{$($c: $c.ok_or_else(|| $crate::serde::de::Error::missing_field(stringify!($c)))?),+})
+		}
+	}
+	deserializer.deserialize_struct(stringify!($Vector), &[$(stringify!($c)),+], Visitor(core::marker::PhantomData))
+} }
+}}
+#[cfg(not(feature="serde"))] #[doc(hidden)] #[macro_export] macro_rules! impl_serde { ($($_:tt)*) => {} }
+
+// Compact [x,y,z] form for a vector field: #[serde(with="vector::serde_array")]. Vectors deserialize from either form by default.
+#[cfg(feature="serde")] pub mod serde_array {
+	use serde::{Serialize, Serializer, Deserialize, Deserializer};
+	pub fn serialize<V:Copy+Into<[T; N]>, T:Serialize, const N: usize, S:Serializer>(v: &V, serializer: S) -> Result<S::Ok, S::Error> where [T; N]: Serialize { Into::<[T; N]>::into(*v).serialize(serializer) }
+	pub fn deserialize<'de, V:From<[T; N]>, T, const N: usize, D:Deserializer<'de>>(deserializer: D) -> Result<V, D::Error> where [T; N]: Deserialize<'de> { <[T; N]>::deserialize(deserializer).map(V::from) }
+}
+
 #[macro_export] macro_rules! vector {
-($N:literal $Vector:ident $($tuple:ident)+, $($c:ident)+, $($C:ident)+) => {
-mod mod_vector {
+($N:literal $Vector:ident $($tuple:ident)+, $($c:ident)+, $($C:ident)+) => { $crate::vector!(mod_vector $N $Vector $($tuple)+, $($c)+, $($C)+); };
+// Explicit module name to define several vectors in the same module
+($module:ident $N:literal $Vector:ident $($tuple:ident)+, $($c:ident)+, $($C:ident)+) => {
+mod $module {
 use core::ops::{Add,Sub,Mul,Div,AddAssign,SubAssign,MulAssign,DivAssign};
 #[allow(non_camel_case_types)]
 #[repr(C)] #[derive(Clone, Copy, Debug, PartialEq, Eq, Default, core::hash::Hash)]
-//#[cfg_attr(feature="serde", derive($crate::serde::Serialize, $crate::serde::Deserialize))]
 pub struct $Vector<T> { $( pub $c: T ),+ }
 //impl<T: Into<U>, U> From<$Vector<T>> for $Vector<U> { fn from(v: $Vector<T>) -> Self { $Vector{$($c:v.$c.into()),+} } } // conflicts with impl<T> From<T> for T
 impl From<$Vector<u8>> for $Vector<u16> { fn from(v: $Vector<u8>) -> Self { $Vector{$($c:v.$c.into()),+} } }
-impl From<$Vector<u16>> for $Vector<u8> { fn from(v: $Vector<u16>) -> Self { $Vector{$($c:v.$c.try_into().unwrap()),+} } }
+impl From<$Vector<u16>> for $Vector<u8> { fn from(v: $Vector<u16>) -> Self { v.saturating_cast() } }
 impl From<$Vector<u16>> for $Vector<u32> { fn from(v: $Vector<u16>) -> Self { $Vector{$($c:v.$c.into()),+} } }
 impl From<$Vector<u32>> for $Vector<f32> { fn from(v: $Vector<u32>) -> Self { $Vector{$($c:v.$c as f32),+} } }
 impl From<$Vector<u16>> for $Vector<f32> { fn from(v: $Vector<u16>) -> Self { $Vector{$($c:v.$c.into()),+} } }
 impl From<$Vector<u8>> for $Vector<f32> { fn from(v: $Vector<u8>) -> Self { $Vector{$($c:v.$c.into()),+} } }
-impl From<$Vector<f32>> for $Vector<u32> { fn from(v: $Vector<f32>) -> Self { $Vector{$($c:unsafe{v.$c.to_int_unchecked()}),+} } }
-impl From<$Vector<f32>> for $Vector<u8> { fn from(v: $Vector<f32>) -> Self { $Vector{$($c:unsafe{v.$c.to_int_unchecked()}),+} } }
+impl From<$Vector<f32>> for $Vector<u32> { fn from(v: $Vector<f32>) -> Self { v.saturating_cast() } }
+impl From<$Vector<f32>> for $Vector<u8> { fn from(v: $Vector<f32>) -> Self { v.saturating_cast() } }
 impl From<$Vector<f32>> for $Vector<f64> { fn from(v: $Vector<f32>) -> Self { $Vector{$($c:v.$c as f64),+} } }
 impl From<$Vector<f64>> for $Vector<f32> { fn from(v: $Vector<f64>) -> Self { $Vector{$($c:v.$c as f32),+} } }
 impl<T> From<$Vector<T>> for [T; $N] { fn from(v : $Vector<T>) -> Self { [$(v.$c),+] } }
@@ -113,6 +244,72 @@ impl<T> $Vector<T> {
 	pub fn each_mut(&mut self) -> [&mut T; $N] { [$(&mut self.$c),+] }
 	pub fn iter(&self) -> core::array::IntoIter<&T, $N> { self.each_ref().into_iter() }
 	pub fn map_mut<U>(&mut self, mut f: impl FnMut(&mut T)->U) -> $Vector<U> { self.each_mut().map(|c| f(c)).into() }
+	// Lossless (From) conversion of each component
+	pub fn as_<U>(self) -> $Vector<U> where T: Into<U> { self.map(Into::into) }
+	pub fn try_cast<U>(self) -> Option<$Vector<U>> where T: $crate::Cast<U> { self.map($crate::Cast::try_cast).transpose() }
+	pub fn saturating_cast<U>(self) -> $Vector<U> where T: $crate::Cast<U> { self.map($crate::Cast::saturating_cast) }
+	pub fn round_to<U>(self) -> $Vector<U> where T: $crate::RoundTo<U> { self.map($crate::RoundTo::round_to) }
+	pub fn floor_to<U>(self) -> $Vector<U> where T: $crate::RoundTo<U> { self.map($crate::RoundTo::floor_to) }
+	pub fn ceil_to<U>(self) -> $Vector<U> where T: $crate::RoundTo<U> { self.map($crate::RoundTo::ceil_to) }
+	pub fn trunc_to<U>(self) -> $Vector<U> where T: $crate::RoundTo<U> { self.map($crate::RoundTo::trunc_to) }
+}
+impl $Vector<f32> { pub fn encode_normalized<U:$crate::Normalized>(self) -> $Vector<U> { self.map(U::encode) } }
+impl<T:$crate::Normalized> $Vector<T> { pub fn decode_normalized(self) -> $Vector<f32> { self.map(T::decode) } }
+
+impl<T:$crate::Float> $Vector<T> {
+	pub fn abs(self) -> Self { self.map(T::abs) }
+	pub fn signum(self) -> Self { self.map(T::signum) }
+	pub fn copysign(self, sign: Self) -> Self { $Vector{$($c: self.$c.copysign(sign.$c)),+} }
+	pub fn recip(self) -> Self { self.map(T::recip) }
+	// Scalar or vector bounds
+	pub fn clamp(self, min: impl Into<Self>, max: impl Into<Self>) -> Self { let (min, max) = (min.into(), max.into()); $Vector{$($c: self.$c.clamp(min.$c, max.$c)),+} }
+	pub fn min_element(self) -> T { self.into_iter().reduce(T::min).unwrap() }
+	pub fn max_element(self) -> T { self.into_iter().reduce(T::max).unwrap() }
+	pub fn is_finite(self) -> $Vector<bool> { self.map(T::is_finite) }
+	pub fn is_nan(self) -> $Vector<bool> { self.map(T::is_nan) }
+	pub fn floor(self) -> Self { self.map(T::floor) }
+	pub fn ceil(self) -> Self { self.map(T::ceil) }
+	pub fn round(self) -> Self { self.map(T::round) }
+	pub fn trunc(self) -> Self { self.map(T::trunc) }
+	pub fn fract(self) -> Self { self.map(T::fract) }
+	pub fn sqrt(self) -> Self { self.map(T::sqrt) }
+	pub fn exp(self) -> Self { self.map(T::exp) }
+	pub fn ln(self) -> Self { self.map(T::ln) }
+	pub fn powf(self, n: T) -> Self { self.map(|c| c.powf(n)) }
+	pub fn mul_add(self, a: Self, b: Self) -> Self { $Vector{$($c: self.$c.mul_add(a.$c, b.$c)),+} }
+}
+impl<T:$crate::Float> $Vector<T> {
+	// None for zero or non finite length (robust_norm also normalizes tiny and huge vectors)
+	pub fn try_normalize(self) -> Option<Self> { let n = $crate::robust_norm(self); (n > T::from(0.) && n.is_finite()).then(|| self.map(|c| c/n)) }
+	pub fn normalize_or_zero(self) -> Self { self.try_normalize().unwrap_or(T::from(0.).into()) }
+	// Mirrors about the plane of unit normal n
+	pub fn reflect(self, n: Self) -> Self { let d = T::from(2.)*$crate::dot(self, n); self - n.map(|c| c*d) }
+	// Unit incident self through the surface of unit normal n (against self), eta: incident over transmitted refractive index. None on total internal reflection
+	pub fn refract(self, n: Self, eta: T) -> Option<Self> {
+		let cos = -$crate::dot(self, n); let k = T::from(1.) - eta*eta*(T::from(1.) - cos*cos);
+		(k >= T::from(0.)).then(|| { let a = eta*cos - k.sqrt(); self.zip(n).map(|(i, n)| eta*i + a*n).collect() })
+	}
+	// Component along b (NaN for zero b)
+	pub fn project_onto(self, b: Self) -> Self { let s = $crate::dot(self, b)/$crate::dot(b, b); b.map(|c| c*s) }
+	pub fn reject_from(self, b: Self) -> Self { self - self.project_onto(b) }
+	// Onto the plane through the origin orthogonal to normal
+	pub fn project_onto_plane(self, normal: Self) -> Self { self.reject_from(normal) }
+	// Unsigned angle in [0, π], accurate near 0 and π (Kahan)
+	pub fn angle(self, b: Self) -> T {
+		let (na, nb) = ($crate::norm(self), $crate::norm(b)); let (u, v) = (self.map(|c| c*nb), b.map(|c| c*na));
+		T::from(2.) * $crate::norm(u-v).atan2($crate::norm(u+v))
+	}
+	// Spherical interpolation of unit vectors at constant angular velocity. Undefined for opposite vectors
+	pub fn slerp(self, b: Self, t: T) -> Self {
+		let angle = self.angle(b); let s = angle.sin();
+		if s <= T::from(f32::EPSILON) { return self.zip(b).map(|(a, b)| a + t*(b-a)).collect::<Self>().normalize_or_zero() }
+		let [sa, sb] = [(T::from(1.)-t)*angle, t*angle].map(|a| a.sin()/s);
+		self.zip(b).map(|(a, b)| sa*a + sb*b).collect()
+	}
+}
+impl $Vector<bool> {
+	pub fn all(self) -> bool { self.into_iter().all(|b| b) }
+	pub fn any(self) -> bool { self.into_iter().any(|b| b) }
 }
 
 impl<T> IntoIterator for $Vector<T> {
@@ -135,6 +332,7 @@ impl<T> core::iter::FromIterator<T> for $Vector<T> { fn from_iter<I:IntoIterator
 #[derive(Clone, Copy)] pub enum Component { $($C),+ }
 impl Component { pub fn enumerate() -> [Self; $N] { [$(Self::$C),+] } }
 impl<T> $Vector<T> { pub fn enumerate() -> [Component; $N] { Component::enumerate() } }
+$crate::impl_serde!{$N $Vector $($c)+}
 impl<T> core::ops::Index<Component> for $Vector<T> {
     type Output = T;
     fn index(&self, component: Component) -> &Self::Output {
@@ -148,6 +346,7 @@ impl<T:Eq> PartialEq<T> for $Vector<T> { fn eq(&self, b: &T) -> bool { self.iter
 impl<T:PartialOrd> PartialOrd for $Vector<T> { fn partial_cmp(&self, b: &Self) -> Option<core::cmp::Ordering> {
 	self.into_iter().zip(b).map(|(a,b)| a.partial_cmp(b)).reduce(|a,e| if a == Some(core::cmp::Ordering::Equal) || a == e { e } else { None }).flatten()
 } }
+impl<T:$crate::ComponentWiseLess> $crate::ComponentWiseLess for $Vector<T> { fn component_wise_lt(&self, b: &Self) -> bool { self.iter().zip(b).all(|(a,b)| a.component_wise_lt(b)) } }
 impl<T:$crate::ComponentWiseMinMax> $crate::ComponentWiseMinMax for $Vector<T> {
 	fn component_wise_min(self, b: Self) -> Self { self.zip(b).map(|(a,b)| a.component_wise_min(b)).collect() }
 	fn component_wise_max(self, b: Self) -> Self { self.zip(b).map(|(a,b)| a.component_wise_max(b)).collect() }
@@ -181,15 +380,142 @@ impl Div<$Vector<f32>> for f32 { type Output=$Vector<f32>; fn div(self, v: $Vect
 impl Div<$Vector<f64>> for f64 { type Output=$Vector<f64>; fn div(self, v: $Vector<f64>) -> Self::Output { $Vector::div(self, v) } }
 
 impl<T: core::iter::Sum> $Vector<T> { pub fn sum(self) -> T { self.into_iter().sum() } }
+// Neumaier compensated summation of each component (Sum folds sequentially)
+impl<T:$crate::Float> $Vector<T> { pub fn compensated_sum(iter: impl IntoIterator<Item=Self>) -> Self {
+	let mut s = $Vector{$($c: $crate::CompensatedSum::<T>::new()),+};
+	for x in iter { $(s.$c += x.$c;)+ }
+	s.map($crate::CompensatedSum::value)
+} }
 
 impl<T> $Vector<Option<T>> {
 	pub fn transpose(self) -> Option<$Vector<T>> { Some($Vector{$($c: self.$c?),+}) } // try_unsigned
 	//pub fn unwrap_or_else(self, f: impl Fn()->T+Copy) -> $Vector<T> { self.map(move |x| x.unwrap_or_else(f)) }
 }
 }
-pub use mod_vector::$Vector;
+pub use $module::$Vector;
 }
 }
 
+#[cfg(not(any(feature="std", feature="libm")))] mod math;
+mod float;
+pub use float::*;
+
+mod sum;
+pub use sum::*;
+
 #[path="xyz.rs"] mod mod_xyz;
 pub use mod_xyz::*;
+
+mod swizzle;
+
+mod predicates;
+pub use predicates::*;
+
+mod polygon;
+pub use polygon::*;
+
+mod segment;
+pub use segment::*;
+
+mod triangulate;
+pub use triangulate::*;
+
+mod matrix;
+pub use matrix::*;
+
+mod quaternion;
+pub use quaternion::*;
+
+mod transform;
+
+mod affine;
+pub use affine::*;
+
+mod color;
+pub use color::*;
+
+#[path="rgb.rs"] mod mod_rgb;
+pub use mod_rgb::*;
+
+mod iter;
+pub use iter::*;
+
+#[cfg(feature="alloc")] mod region;
+#[cfg(feature="alloc")] pub use region::*;
+#[cfg(feature="alloc")] mod image;
+#[cfg(feature="alloc")] pub use image::*;
+#[cfg(feature="alloc")] mod hull;
+#[cfg(feature="alloc")] pub use hull::*;
+
+#[cfg(test)] mod test {
+	use crate::{xy, xyz};
+	#[cfg(feature="serde")] use crate::{xyzw, vec2, vec3, MinMax, Rect};
+	#[test] fn narrowing() {
+		assert_eq!(xyz::<u8>::from(xyz{x: 0u16, y: 255, z: 256}), xyz{x: 0, y: 255, z: 255}); assert_eq!(xy::<u8>::from(xy{x: u16::MAX, y: 7}), xy{x: 255, y: 7});
+		assert_eq!(xy::<u8>::from(xy{x: -1f32, y: 300.}), xy{x: 0, y: 255}); assert_eq!(xy{x: 256u16, y: 1}.try_cast::<u8>(), None);
+	}
+	#[test] fn float_cast() {
+		use crate::Cast;
+		assert_eq!(Cast::<f32>::try_cast(f64::NAN), None); assert_eq!(Cast::<f64>::try_cast(f32::NAN), None); assert_eq!(xy{x: 1f64, y: f64::NAN}.try_cast::<f32>(), None);
+		assert_eq!(Cast::<f32>::try_cast(f64::INFINITY), Some(f32::INFINITY)); assert_eq!(Cast::<f32>::try_cast(f64::NEG_INFINITY), Some(f32::NEG_INFINITY)); assert_eq!(Cast::<f64>::try_cast(f32::NEG_INFINITY), Some(f64::NEG_INFINITY));
+		assert_eq!(Cast::<f32>::try_cast(1e300f64), None); assert_eq!(Cast::<f32>::try_cast(-1e300f64), None); assert_eq!(Cast::<f32>::try_cast(f32::MAX as f64), Some(f32::MAX));
+		assert_eq!(Cast::<f32>::try_cast(1e-300f64), Some(0.)); assert_eq!(Cast::<f32>::try_cast(0.1f64), Some(0.1f32)); assert_eq!(Cast::<f64>::try_cast(0.1f32), Some(0.1f32 as f64));
+		assert_eq!(Cast::<f32>::saturating_cast(1e300f64), f32::MAX); assert_eq!(Cast::<f32>::saturating_cast(-1e300f64), f32::MIN); assert_eq!(Cast::<f32>::saturating_cast(f64::INFINITY), f32::INFINITY);
+		assert_eq!(Cast::<i32>::try_cast(f32::NAN), None); assert_eq!(Cast::<u8>::try_cast(f64::INFINITY), None); assert_eq!(Cast::<i8>::saturating_cast(f32::NAN), 0);
+	}
+	#[test] fn normalized() {
+		use crate::{xyzw, Normalized};
+		for i in 0..=u8::MAX { assert_eq!(u8::encode(i.decode()), i); } for i in 0..=u16::MAX { assert_eq!(u16::encode(i.decode()), i); }
+		for i in -i8::MAX..=i8::MAX { assert_eq!(i8::encode(i.decode()), i); } for i in -i16::MAX..=i16::MAX { assert_eq!(i16::encode(i.decode()), i); }
+		assert_eq!([i8::MIN.decode(), (-i8::MAX).decode(), i16::MIN.decode(), (-i16::MAX).decode()], [-1.; 4]); assert_eq!((i8::encode(-1.), i16::encode(-1.)), (-i8::MAX, -i16::MAX)); // MIN decodes as -MAX
+		assert_eq!([u8::MAX.decode(), u16::MAX.decode(), i8::MAX.decode(), i16::MAX.decode()], [1.; 4]); assert_eq!([0u8.decode(), 0i8.decode()], [0., 0.]);
+		let v = xyzw{x: 0.5f32, y: -0.2, z: 1.5, w: f32::NAN}; // Clamped and NaN to 0
+		assert_eq!(v.encode_normalized::<u8>(), xyzw{x: 128, y: 0, z: 255, w: 0}); assert_eq!(v.encode_normalized::<u16>(), xyzw{x: 32768, y: 0, z: 65535, w: 0});
+		assert_eq!(v.encode_normalized::<i8>(), xyzw{x: 64, y: -25, z: 127, w: 0}); assert_eq!(v.encode_normalized::<i16>(), xyzw{x: 16384, y: -6553, z: 32767, w: 0});
+		assert_eq!([i8::encode(-2.), i8::encode(f32::NEG_INFINITY), i8::encode(f32::INFINITY)], [-127, -127, 127]); assert_eq!([u16::encode(-0.), u16::encode(f32::INFINITY)], [0, 65535]);
+		assert_eq!(xyzw{x: -128i8, y: -127, z: 127, w: 0}.decode_normalized(), xyzw{x: -1., y: -1., z: 1., w: 0.});
+	}
+	#[test] fn geometry() {
+		use crate::{vec3, dot, norm, normalize, cross};
+		let close = |a: vec3, b: vec3| (a-b).into_iter().all(|e| e.abs() < 1e-5);
+		let n = xyz{x: 0., y: 1., z: 0.}; let i = normalize(xyz{x: 1f32, y: -1., z: 0.}); // 45° incidence
+		assert_eq!(xyz{x: 1., y: -1., z: 0.}.reflect(n), xyz{x: 1., y: 1., z: 0.}); assert!(close(i.refract(n, 1.).unwrap(), i));
+		let t = i.refract(n, 1./1.5).unwrap(); assert!((t.x - 0.5f32.sqrt()/1.5).abs() < 1e-6 && t.y < 0. && (norm(t) - 1.).abs() < 1e-6); // Snell
+		assert_eq!(i.refract(n, 1.5), None); assert_eq!(i.refract(n, 1.42), None); assert!(i.refract(n, 1.41).is_some()); // Critical angle asin(1/η): 45° for η = √2
+		assert!(close(-n.refract(n, 1.5).unwrap(), n)); // Normal incidence never reflects totally
+		let zero = xyz{x: 0f32, y: 0., z: 0.};
+		assert_eq!(zero.try_normalize(), None); assert_eq!(zero.normalize_or_zero(), zero); assert_eq!(xy{x: 3f32, y: 4.}.try_normalize(), Some(xy{x: 0.6, y: 0.8}));
+		assert_eq!(xy{x: f32::INFINITY, y: 0.}.try_normalize(), None); assert_eq!(xy{x: f32::NAN, y: 0.}.try_normalize(), None); assert_eq!(xy{x: 1e-30f32, y: 0.}.try_normalize(), Some(xy{x: 1., y: 0.})); let u = xy{x: 3e30f32, y: -4e30}.try_normalize().unwrap(); assert!((u.x - 0.6).abs() < 1e-6 && (u.y + 0.8).abs() < 1e-6);
+		let v = xyz{x: 3., y: 4., z: 5.};
+		assert_eq!(v.project_onto(xyz{x: 2., y: 0., z: 0.}), xyz{x: 3., y: 0., z: 0.}); assert_eq!(v.reject_from(n), xyz{x: 3., y: 0., z: 5.}); assert_eq!(v.project_onto_plane(n), xyz{x: 3., y: 0., z: 5.});
+		assert_eq!(zero.project_onto(v), zero); assert_eq!(zero.reject_from(v), zero); assert!(v.project_onto(zero).is_nan().into_iter().all(|nan| nan)); // Zero b is undefined
+		for n in [xyz{x: 0., y: 0., z: 1.}, xyz{x: 0., y: 0., z: -1.}, n, xyz{x: 1., y: 0., z: 0.}, normalize(xyz{x: 1f32, y: 2., z: 3.}), normalize(xyz{x: -1f32, y: 0.5, z: -0.1}), normalize(xyz{x: 1f32, y: 1e-3, z: -1e-7})] {
+			let (t, b) = n.orthonormal_basis();
+			for (u, v) in [(t, n), (b, n), (t, b)] { assert!(dot(u, v).abs() < 1e-6, "{n:?}"); } for u in [t, b] { assert!((norm(u) - 1.).abs() < 1e-6, "{n:?}"); }
+			assert!(close(cross(t, b), n), "{n:?}"); // Right handed
+		}
+	}
+	#[test] fn robust_norm() {
+		use crate::{norm, robust_norm, robust_normalize};
+		assert_eq!(norm(xy{x: 1e200, y: 1e200}), f64::INFINITY); assert!((robust_norm(xy{x: 1e200, y: 1e200})/1e200 - core::f64::consts::SQRT_2).abs() < 1e-15);
+		assert_eq!(norm(xy{x: 3e-200, y: 4e-200}), 0.); assert_eq!(robust_norm(xy{x: 3e-200, y: 4e-200}), 5e-200); assert_eq!(robust_norm([3f32, 4.]), 5.);
+		assert_eq!(robust_norm(xyz{x: 0f32, y: 0., z: 0.}), 0.); assert_eq!(robust_norm(xy{x: f64::INFINITY, y: 1.}), f64::INFINITY); assert!(robust_norm(xy{x: f64::NAN, y: 1.}).is_nan());
+		let u: xy<f64> = robust_normalize(xy{x: -1e200, y: 1e200}); assert!((u.x + core::f64::consts::FRAC_1_SQRT_2).abs() < 1e-15 && u.x == -u.y);
+	}
+	#[cfg(feature="serde")] #[derive(serde::Serialize, serde::Deserialize, PartialEq, Debug)] struct S { #[serde(with="crate::serde_array")] p: vec3, q: xyzw<u8>, r: Rect, b: MinMax<vec2> }
+	#[cfg(feature="serde")] #[test] fn serde() {
+		let v = xy{x: 1.5f32, y: -2.};
+		let json = serde_json::to_string(&v).unwrap(); assert_eq!(json, r#"{"x":1.5,"y":-2.0}"#);
+		assert_eq!(serde_json::from_str::<vec2>(&json).unwrap(), v);
+		assert_eq!(serde_json::from_str::<vec2>("[1.5,-2]").unwrap(), v);
+		assert_eq!(serde_json::from_str::<vec3>(r#"{"z":3,"x":1,"y":2}"#).unwrap(), xyz{x: 1., y: 2., z: 3.});
+		for malformed in ["[1.5]", "[1.5,-2,3]", "[]", r#"{"x":1}"#, r#"{"x":1,"x":2,"y":1}"#, r#"{"x":1,"y":2,"w":3}"#, r#"{"x":"1","y":2}"#, "1"] { assert!(serde_json::from_str::<vec2>(malformed).is_err(), "{malformed}"); }
+		let s = S{p: xyz{x: 1., y: 2., z: 3.}, q: xyzw{x: 1, y: 2, z: 3, w: 4}, r: Rect{min: xy{x: -1, y: 0}, max: xy{x: 3, y: 4}}, b: MinMax{min: xy{x: 0., y: 0.5}, max: xy{x: 1., y: 2.}}};
+		let json = serde_json::to_string(&s).unwrap();
+		assert_eq!(json, r#"{"p":[1.0,2.0,3.0],"q":{"x":1,"y":2,"z":3,"w":4},"r":{"min":{"x":-1,"y":0},"max":{"x":3,"y":4}},"b":{"min":{"x":0.0,"y":0.5},"max":{"x":1.0,"y":2.0}}}"#);
+		assert_eq!(serde_json::from_str::<S>(&json).unwrap(), s);
+		assert!(serde_json::from_str::<S>(&json.replace("[1.0,2.0,3.0]", "[1.0,2.0]")).is_err()); assert!(serde_json::from_str::<S>(&json.replace(r#"{"x":1.0,"y":2.0}"#, r#"{"x":1.0}"#)).is_err());
+		let r: Rect = serde_json::from_str("[[0,0],[1,2]]").unwrap(); assert_eq!(r, MinMax{min: xy{x: 0, y: 0}, max: xy{x: 1, y: 2}});
+		assert!(serde_json::from_str::<Rect>(r#"{"min":[0,0]}"#).is_err()); assert!(serde_json::from_str::<Rect>("[[0,0],[1,2,3]]").is_err());
+	}
+}
@@ -0,0 +1,180 @@
+// Pure core fallbacks (without std nor libm) in f64. f32 evaluates through these and rounds once, within 1 ulp.
+// f64 within 4 ulp (sin, cos and tan over the whole range), powf within 4+|y ln x| ulp, and mul_add which rounds twice.
+use core::f64::consts::{FRAC_PI_2, FRAC_PI_4, LN_2};
+
+const TWO_52: f64 = 4503599627370496.;
+
+pub fn trunc(x: f64) -> f64 { if x.abs() < TWO_52 { (x as i64 as f64).copysign(x) } else { x } } // Also NaN and infinities
+pub fn floor(x: f64) -> f64 { let t = trunc(x); if t > x { t - 1. } else { t } }
+pub fn ceil(x: f64) -> f64 { let t = trunc(x); if t < x { t + 1. } else { t } }
+pub fn round(x: f64) -> f64 { let t = trunc(x); if (x - t).abs() >= 0.5 { t + x.signum() } else { t } } // x - t is exact
+
+pub fn sqrt(x: f64) -> f64 {
+	if x < 0. { return f64::NAN }
+	if x == 0. || !x.is_finite() { return x }
+	if x < f64::MIN_POSITIVE { return sqrt(x * TWO_52 * TWO_52) / TWO_52 }
+	let mut y = f64::from_bits((x.to_bits() >> 1) + 0x1FF8_0000_0000_0000); // Halves the exponent, within 6%
+	for _ in 0..5 { y = (y + x/y) / 2.; }
+	y
+}
+pub fn cbrt(x: f64) -> f64 {
+	if x == 0. || !x.is_finite() { return x }
+	let y = exp(ln(x.abs())/3.).copysign(x);
+	y - (y - x/(y*y))/3. // Newton
+}
+
+// 2^k as a power of two (k in the normal and subnormal range)
+fn exp2i(k: i32) -> f64 {
+	if k > 1023 { f64::INFINITY } else if k >= -1022 { f64::from_bits(((k + 1023) as u64) << 52) } else if k >= -1074 { f64::from_bits(1 << (k + 1074)) } else { 0. }
+}
+// ln 2 = LN_2_HI + LN_2_LO where k*LN_2_HI is exact
+const LN_2_HI: f64 = 0.693_147_180_369_123_8;
+const LN_2_LO: f64 = 1.908_214_929_270_587_7e-10;
+pub fn exp(x: f64) -> f64 {
+	if x.is_nan() { return x }
+	if x > 709.8 { return f64::INFINITY }
+	if x < -745.2 { return 0. }
+	let k = round(x / LN_2);
+	let r = (x - k*LN_2_HI) - k*LN_2_LO; // |r| <= ln2/2
+	let p = (1..=13).rev().fold(1., |p, n| 1. + p*r/n as f64); // Taylor
+	let k = k as i32; // Splits the scale to avoid overflowing 2^k before multiplying by p < 2
+	p * exp2i(k/2) * exp2i(k - k/2)
+}
+pub fn ln(x: f64) -> f64 {
+	if x.is_nan() || x < 0. { return f64::NAN }
+	if x == 0. { return f64::NEG_INFINITY }
+	if x == f64::INFINITY { return x }
+	let (x, e) = if x < f64::MIN_POSITIVE { (x * TWO_52, -52) } else { (x, 0) };
+	let bits = x.to_bits();
+	let mut e = e + ((bits >> 52) as i32 - 1023);
+	let mut m = f64::from_bits((bits & 0x000F_FFFF_FFFF_FFFF) | 0x3FF0_0000_0000_0000); // [1, 2)
+	if m > core::f64::consts::SQRT_2 { m /= 2.; e += 1; }
+	let s = (m - 1.) / (m + 1.); let s2 = s*s; // |s| <= 0.172
+	let atanh = s * (0..=10).rev().fold(0., |p, n| 1./(2*n+1) as f64 + p*s2);
+	let e = e as f64;
+	e*LN_2_HI + (2.*atanh + e*LN_2_LO)
+}
+pub fn powf(x: f64, y: f64) -> f64 {
+	if y == 0. || x == 1. { return 1. }
+	if x.is_nan() || y.is_nan() { return f64::NAN }
+	let odd = trunc(y) == y && y.abs() < TWO_52 && (y as i64) % 2 != 0;
+	if x < 0. { return if trunc(y) == y { let p = powf(-x, y); if odd { -p } else { p } } else { f64::NAN } }
+	if x == 0. { return if y > 0. { if odd { x } else { 0. } } else if odd { f64::INFINITY.copysign(x) } else { f64::INFINITY } }
+	exp(y * ln(x))
+}
+pub fn mul_add(x: f64, a: f64, b: f64) -> f64 { x*a + b }
+
+// Argument reduction: x = k π/2 + r, |r| <= π/4, only k mod 4 is significant.
+// Below 2^20, π/2 in three parts (33 + 33 + 53 bits), so that k times the first two is exact
+const PIO2_1: f64 = 1.570_796_326_734_125_6;
+const PIO2_2: f64 = 6.077_100_506_303_966e-11;
+const PIO2_3: f64 = 2.022_266_248_795_950_6e-21;
+// Beyond, Payne-Hanek: only the bits of 2/π which are significant for x 2/π mod 4
+const TWO_OVER_PI: [u32; 40] = [
+	0xA2F9836E, 0x4E441529, 0xFC2757D1, 0xF534DDC0, 0xDB629599, 0x3C439041, 0xFE5163AB, 0xDEBBC561, 0xB7246E3A, 0x424DD2E0,
+	0x06492EEA, 0x09D1921C, 0xFE1DEB1C, 0xB129A73E, 0xE88235F5, 0x2EBB4484, 0xE99C7026, 0xB45F7E41, 0x3991D639, 0x835339F4,
+	0x9C845F8B, 0xBDF9283B, 0x1FF897FF, 0xDE05980F, 0xEF2F118B, 0x5A0A6D1F, 0x6D367ECF, 0x27CB09B7, 0x4F463F66, 0x9E5FEA2D,
+	0x7527BAC7, 0xEBE5F17B, 0x3D0739F7, 0x8A5292EA, 0x6BFB5FB1, 0x1F8D5D08, 0x56033046, 0xFC7B6BAB, 0xF0CFBC20, 0x9AF4361D];
+fn reduce(x: f64) -> (i64, f64) {
+	if x.abs() < 1048576. { let k = round(x / FRAC_PI_2); return (k as i64, ((x - k*PIO2_1) - k*PIO2_2) - k*PIO2_3) }
+	if x < 0. { let (k, r) = reduce(-x); return (-k, -r) }
+	// x = m 2^e, bits of 2/π after the s-th, where earlier bits contribute multiples of 8
+	let bits = x.to_bits();
+	let (m, e) = ((bits & 0x000F_FFFF_FFFF_FFFF) | 1 << 52, (bits >> 52) as i32 - 1075);
+	let s = (e - 3).max(0) as usize;
+	let word = |i: usize| { let (w, b) = (s/32 + i, s%32); if b == 0 { TWO_OVER_PI[w] } else { TWO_OVER_PI[w] << b | TWO_OVER_PI[w+1] >> (32 - b) } };
+	// m times 192 bits of 2/π (little endian limbs): x 2/π = p 2^(e-s-192)
+	let mut p = [0u32; 8];
+	for (i, m) in [m as u32, (m >> 32) as u32].into_iter().enumerate() {
+		let mut carry = 0u64;
+		for j in 0..6 { let t = p[i+j] as u64 + m as u64 * word(5-j) as u64 + carry; p[i+j] = t as u32; carry = t >> 32; }
+		p[i+6] = carry as u32;
+	}
+	let [lo, hi] = [0, 4].map(|i| p[i..i+4].iter().rev().fold(0u128, |a, &w| a << 32 | w as u128));
+	// Integer part mod 4 and the first 128 bits of the fraction
+	let t = (64 + s as i32 - e) as u32; // 61..=96
+	let (mut k, mut f) = ((hi >> t) as i64 & 3, hi << (128 - t) | lo >> t);
+	let negative = f >> 127 != 0; // Fraction >= 1/2: towards the next multiple
+	if negative { k += 1; f = f.wrapping_neg(); }
+	let f_hi = f as f64; let f_lo = f.wrapping_sub(f_hi as u128) as i128 as f64; // Rounding error of f_hi
+	const PIO2_LO: f64 = 6.123_233_995_736_766e-17; // π/2 - FRAC_PI_2
+	let scale = 1. / 340_282_366_920_938_463_463_374_607_431_768_211_456.; // 2^-128
+	let r = (f_hi*scale)*FRAC_PI_2 + ((f_lo*scale)*FRAC_PI_2 + (f_hi*scale)*PIO2_LO);
+	(k, if negative { -r } else { r })
+}
+fn sin_kernel(r: f64) -> f64 { let r2 = r*r; r * (1..=8).rev().fold(1., |p, n| 1. - p*r2/((2*n)*(2*n+1)) as f64) } // Taylor to r^17
+fn cos_kernel(r: f64) -> f64 { let r2 = r*r; (1..=9).rev().fold(1., |p, n| 1. - p*r2/((2*n-1)*(2*n)) as f64) } // Taylor to r^18
+pub fn sin_cos(x: f64) -> (f64, f64) {
+	if !x.is_finite() { return (f64::NAN, f64::NAN) }
+	let (k, r) = reduce(x);
+	let (s, c) = (sin_kernel(r), cos_kernel(r));
+	match k & 3 { 0 => (s, c), 1 => (c, -s), 2 => (-s, -c), _ => (-c, s) }
+}
+pub fn sin(x: f64) -> f64 { sin_cos(x).0 }
+pub fn cos(x: f64) -> f64 { sin_cos(x).1 }
+pub fn tan(x: f64) -> f64 { let (s, c) = sin_cos(x); s / c }
+
+pub fn atan(x: f64) -> f64 {
+	if x.is_nan() { return x }
+	if x.abs() < 1e-9 { return x } // x^3/3 is negligible, and avoids subnormal halvings
+	if x.abs() > 1. { return FRAC_PI_2.copysign(x) - atan(1./x) }
+	let mut t = x;
+	for _ in 0..3 { t /= 1. + sqrt(1. + t*t); } // Halves the angle: |t| <= tan(π/32)
+	let t2 = t*t;
+	8. * t * (0..=9).rev().fold(0., |p, n| (if n % 2 == 0 { 1. } else { -1. }) / (2*n+1) as f64 + p*t2)
+}
+pub fn atan2(y: f64, x: f64) -> f64 {
+	if x.is_nan() || y.is_nan() { return f64::NAN }
+	if x == 0. { return if y == 0. { if x.is_sign_negative() { core::f64::consts::PI.copysign(y) } else { y } } else { FRAC_PI_2.copysign(y) } }
+	if x.is_infinite() && y.is_infinite() { return (if x > 0. { FRAC_PI_4 } else { 3.*FRAC_PI_4 }).copysign(y) }
+	let a = atan(y / x);
+	if x > 0. { a } else { a + core::f64::consts::PI.copysign(y) }
+}
+pub fn asin(x: f64) -> f64 { if x.abs() > 1. { f64::NAN } else { atan2(x, sqrt((1. - x)*(1. + x))) } }
+pub fn acos(x: f64) -> f64 { if x.abs() > 1. { f64::NAN } else { atan2(sqrt((1. - x)*(1. + x)), x) } }
+
+#[cfg(test)] mod test {
+	extern crate std;
+	fn ulp(a: f64, b: f64) -> f64 { if a == b { 0. } else { (a-b).abs() / (b.abs().max(f64::MIN_POSITIVE) * f64::EPSILON) } } // Constant below MIN_POSITIVE
+	#[test] fn large_arguments() {
+		let mut seed = 0x9E3779B97F4A7C15u64;
+		for _ in 0..100000 {
+			seed ^= seed << 13; seed ^= seed >> 7; seed ^= seed << 17;
+			let x = f64::from_bits(0x4130_0000_0000_0000 + seed % (0x7FF0_0000_0000_0000 - 0x4130_0000_0000_0000)); // [2^20, max]
+			for x in [x, -x] {
+				let (s, c) = super::sin_cos(x);
+				assert!(ulp(s, x.sin()) <= 4. && ulp(c, x.cos()) <= 4., "{x}: {s} {c} != {} {}", x.sin(), x.cos());
+				let x = x as f32; if x.is_infinite() { continue } let (s, c) = super::sin_cos(x as f64);
+				assert!((s as f32, c as f32) == ((x as f64).sin() as f32, (x as f64).cos() as f32) || (ulp(s, (x as f64).sin()) <= 4. && ulp(c, (x as f64).cos()) <= 4.), "{x}");
+			}
+		}
+		// Closest f64 to a multiple of π/2 (Muller): std is off by a few ulp, x mod π/2 = 4.687165924254628e-19 (to 17 digits)
+		let x = 6381956970095103. * 2f64.powi(797); assert_eq!(super::sin_cos(x), (1., -4.687165924254628e-19));
+		assert!(ulp(super::sin(1e15), 1e15f64.sin()) <= 4. && ulp(super::sin(1e19), 1e19f64.sin()) <= 4. && ulp(super::sin(f32::MAX as f64), (f32::MAX as f64).sin()) <= 4.);
+		assert!(ulp(super::tan(1e300), 1e300f64.tan()) <= 4. && ulp(super::sin(f64::MAX), f64::MAX.sin()) <= 4.);
+	}
+	// f64 within 4 ulp of std (which is itself within 1), f32 rounded once from f64 within 1 ulp of std
+	fn f32_ulp(a: f32, b: f32) -> u32 { if a == b || (a.is_nan() && b.is_nan()) { 0 } else { (a.to_bits() as i32).abs_diff(b.to_bits() as i32) } }
+	fn check(name: &str, f: fn(f64) -> f64, g: fn(f64) -> f64, x: f64, bound: f64) {
+		let (a, b) = (f(x), g(x)); assert!(ulp(a, b) <= bound || (a.is_nan() && b.is_nan()), "{name}({x}) = {a} != {b}");
+		let x = x as f32; let (a, b) = (f(x as f64) as f32, g(x as f64) as f32); assert!(f32_ulp(a, b) <= 1, "{name}({x}) = {a} != {b} (f32)");
+	}
+	#[test] fn accuracy() {
+		let mut seed = 0x2545F4914F6CDD1Du64; let mut random = move || { seed ^= seed << 13; seed ^= seed >> 7; seed ^= seed << 17; (seed >> 11) as f64 / (1u64 << 53) as f64 }; // [0, 1)
+		for _ in 0..100000 {
+			let t = random();
+			check("exp", super::exp, f64::exp, -740. + 1449.*t, 4.); check("exp", super::exp, f64::exp, 40.*t - 20., 4.);
+			let x = f64::from_bits((random() * 0x7FF0_0000_0000_0000u64 as f64) as u64); // Any positive finite
+			check("ln", super::ln, f64::ln, x, 4.); check("ln", super::ln, f64::ln, 0.5 + t, 4.); check("sqrt", super::sqrt, f64::sqrt, x, 4.); check("cbrt", super::cbrt, f64::cbrt, -x, 4.);
+			check("atan", super::atan, f64::atan, x, 4.); check("atan", super::atan, f64::atan, 4.*t - 2., 4.);
+			check("asin", super::asin, f64::asin, 2.*t - 1., 4.); check("acos", super::acos, f64::acos, 2.*t - 1., 4.);
+			check("tan", super::tan, f64::tan, 2e6*t - 1e6, 4.); check("tan", super::tan, f64::tan, 4.*t - 2., 4.);
+			let (x, y) = (1e3*random(), 100.*random() - 50.); let a = super::powf(x, y); let b = x.powf(y);
+			assert!(ulp(a, b) <= 4. + (y*x.ln()).abs() || (a.is_infinite() && b > f64::MAX/2.) || (b.is_infinite() && a > f64::MAX/2.), "powf({x}, {y}) = {a} != {b}");
+		}
+		for (x, e) in [(0., 1.), (-0., 1.), (f64::INFINITY, f64::INFINITY), (f64::NEG_INFINITY, 0.), (710., f64::INFINITY), (-746., 0.)] { assert_eq!(super::exp(x), e); }
+		assert!(super::exp(f64::NAN).is_nan() && super::ln(-1.).is_nan() && super::sqrt(-1.).is_nan() && super::asin(1.5).is_nan() && super::acos(-1.5).is_nan() && super::powf(-2., 0.5).is_nan());
+		assert_eq!([super::ln(0.), super::ln(1.), super::sqrt(-0.), super::cbrt(-8.), super::atan(f64::INFINITY), super::asin(-1.), super::acos(-1.)], [f64::NEG_INFINITY, 0., -0., -2., core::f64::consts::FRAC_PI_2, -core::f64::consts::FRAC_PI_2, core::f64::consts::PI]);
+		assert_eq!([super::powf(0., -1.), super::powf(-0., -3.), super::powf(2., -1075.), super::powf(f64::NAN, 0.)], [f64::INFINITY, f64::NEG_INFINITY, 0., 1.]); assert!(ulp(super::powf(-2., 3.), -8.) <= 4. + 3.*super::LN_2);
+	}
+}
@@ -0,0 +1,96 @@
+use core::ops::{Add,Sub,Mul,Neg,AddAssign,SubAssign,MulAssign,Index,IndexMut};
+use core::array::from_fn as eval;
+use num::Zero;
+use crate::{xy, xyz, xyzw, Det};
+
+// M rows of N columns
+#[repr(C)] #[derive(Clone, Copy, Debug, PartialEq, Eq, core::hash::Hash)] pub struct Matrix<T, const M: usize, const N: usize>(pub [[T; N]; M]);
+#[allow(non_camel_case_types)] pub type mat3 = Matrix<f32, 3, 3>;
+#[allow(non_camel_case_types)] pub type mat4x4 = Matrix<f32, 4, 4>; // mat4 is xyzw<vec4>
+
+unsafe impl<T: bytemuck::Zeroable, const M: usize, const N: usize> bytemuck::Zeroable for Matrix<T, M, N> {}
+unsafe impl<T: bytemuck::Pod, const M: usize, const N: usize> bytemuck::Pod for Matrix<T, M, N> {}
+impl<T: Zero, const M: usize, const N: usize> Zero for Matrix<T, M, N> { const ZERO: Self = Matrix([const { [T::ZERO; N] }; M]); }
+
+#[cfg(feature="serde")] impl<T, const M: usize, const N: usize> serde::Serialize for Matrix<T, M, N> where [[T; N]; M]: serde::Serialize {
+	fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> { self.0.serialize(serializer) }
+}
+#[cfg(feature="serde")] impl<'de, T, const M: usize, const N: usize> serde::Deserialize<'de> for Matrix<T, M, N> where [[T; N]; M]: serde::Deserialize<'de> {
+	fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> { <[[T; N]; M]>::deserialize(deserializer).map(Matrix) }
+}
+
+impl<T, const M: usize, const N: usize> From<[[T; N]; M]> for Matrix<T, M, N> { fn from(m: [[T; N]; M]) -> Self { Matrix(m) } }
+impl<T, const M: usize, const N: usize> From<Matrix<T, M, N>> for [[T; N]; M] { fn from(m: Matrix<T, M, N>) -> Self { m.0 } }
+// xyz/xyzw of rows
+impl<T> From<xyz<xyz<T>>> for Matrix<T, 3, 3> { fn from(m: xyz<xyz<T>>) -> Self { Matrix(<[_; 3]>::from(m).map(<[T; 3]>::from)) } }
+impl<T> From<Matrix<T, 3, 3>> for xyz<xyz<T>> { fn from(Matrix(m): Matrix<T, 3, 3>) -> Self { m.map(xyz::from).into() } }
+impl<T> From<xyzw<xyzw<T>>> for Matrix<T, 4, 4> { fn from(m: xyzw<xyzw<T>>) -> Self { Matrix(<[_; 4]>::from(m).map(<[T; 4]>::from)) } }
+impl<T> From<Matrix<T, 4, 4>> for xyzw<xyzw<T>> { fn from(Matrix(m): Matrix<T, 4, 4>) -> Self { m.map(xyzw::from).into() } }
+// Upper-left 3x3 of a 4x4 (linear part of an affine transform)
+impl<T: Copy> From<Matrix<T, 4, 4>> for Matrix<T, 3, 3> { fn from(Matrix(m): Matrix<T, 4, 4>) -> Self { Matrix(eval(|i| eval(|j| m[i][j]))) } }
+// Upper-left 3x3 of an identity 4x4
+impl<T: Zero+From<u8>+Copy> From<Matrix<T, 3, 3>> for Matrix<T, 4, 4> { fn from(Matrix(m): Matrix<T, 3, 3>) -> Self {
+	Matrix(eval(|i| eval(|j| if i < 3 && j < 3 { m[i][j] } else if i == j { 1.into() } else { T::ZERO })))
+} }
+
+impl<T, const M: usize, const N: usize> Index<usize> for Matrix<T, M, N> { type Output = [T; N]; fn index(&self, i: usize) -> &Self::Output { &self.0[i] } }
+impl<T, const M: usize, const N: usize> IndexMut<usize> for Matrix<T, M, N> { fn index_mut(&mut self, i: usize) -> &mut Self::Output { &mut self.0[i] } }
+
+impl<T: Copy, const M: usize, const N: usize> Matrix<T, M, N> {
+	pub fn transpose(self) -> Matrix<T, N, M> { Matrix(crate::transpose(self.0)) }
+	pub fn column(self, j: usize) -> [T; M] { self.0.map(|row| row[j]) }
+	pub fn map<U>(self, mut f: impl FnMut(T)->U) -> Matrix<U, M, N> { Matrix(self.0.map(|row| row.map(&mut f))) }
+}
+impl<T: Zero+Copy, const N: usize> Matrix<T, N, N> {
+	pub fn diagonal(diagonal: [T; N]) -> Self { Matrix(eval(|i| eval(|j| if i==j { diagonal[i] } else { T::ZERO }))) }
+}
+impl<T: Zero+From<u8>+Copy, const N: usize> Matrix<T, N, N> { pub fn identity() -> Self { Self::diagonal([1.into(); N]) } }
+impl<const N: usize> Matrix<f32, N, N> where [[f32; N]; N]: Det {
+	pub fn det(self) -> f32 { crate::det(self.0) }
+	pub fn adjugate(self) -> Self { Matrix(crate::adjugate(self.0)) }
+	pub fn inverse(self) -> Option<Self> { crate::inverse(self.0).map(Matrix) }
+}
+
+impl<T: Add<Output=T>+Copy, const M: usize, const N: usize> Add for Matrix<T, M, N> { type Output = Self; fn add(self, b: Self) -> Self { Matrix(eval(|i| eval(|j| self.0[i][j]+b.0[i][j]))) } }
+impl<T: Sub<Output=T>+Copy, const M: usize, const N: usize> Sub for Matrix<T, M, N> { type Output = Self; fn sub(self, b: Self) -> Self { Matrix(eval(|i| eval(|j| self.0[i][j]-b.0[i][j]))) } }
+impl<T: Neg<Output=T>+Copy, const M: usize, const N: usize> Neg for Matrix<T, M, N> { type Output = Self; fn neg(self) -> Self { self.map(Neg::neg) } }
+impl<T: Add<Output=T>+Copy, const M: usize, const N: usize> AddAssign for Matrix<T, M, N> { fn add_assign(&mut self, b: Self) { *self = *self + b } }
+impl<T: Sub<Output=T>+Copy, const M: usize, const N: usize> SubAssign for Matrix<T, M, N> { fn sub_assign(&mut self, b: Self) { *self = *self - b } }
+
+impl<T: Zero+Add<Output=T>+Mul<Output=T>+Copy, const M: usize, const N: usize, const P: usize> Mul<Matrix<T, N, P>> for Matrix<T, M, N> { type Output = Matrix<T, M, P>;
+	fn mul(self, b: Matrix<T, N, P>) -> Self::Output { Matrix(eval(|i| eval(|j| (0..N).fold(T::ZERO, |sum, k| sum + self.0[i][k]*b.0[k][j])))) }
+}
+impl<T: Zero+Add<Output=T>+Mul<Output=T>+Copy, const N: usize> MulAssign for Matrix<T, N, N> { fn mul_assign(&mut self, b: Self) { *self = *self * b } }
+impl<T: Zero+Add<Output=T>+Mul<Output=T>+Copy, const M: usize, const N: usize> Mul<[T; N]> for Matrix<T, M, N> { type Output = [T; M];
+	fn mul(self, v: [T; N]) -> Self::Output { self.0.map(|row| (0..N).fold(T::ZERO, |sum, k| sum + row[k]*v[k])) }
+}
+impl<T: Zero+Add<Output=T>+Mul<Output=T>+Copy> Mul<xy<T>> for Matrix<T, 2, 2> { type Output = xy<T>; fn mul(self, v: xy<T>) -> Self::Output { (self * <[T; 2]>::from(v)).into() } }
+impl<T: Zero+Add<Output=T>+Mul<Output=T>+Copy> Mul<xyz<T>> for Matrix<T, 3, 3> { type Output = xyz<T>; fn mul(self, v: xyz<T>) -> Self::Output { (self * <[T; 3]>::from(v)).into() } }
+impl<T: Zero+Add<Output=T>+Mul<Output=T>+Copy> Mul<xyzw<T>> for Matrix<T, 4, 4> { type Output = xyzw<T>; fn mul(self, v: xyzw<T>) -> Self::Output { (self * <[T; 4]>::from(v)).into() } }
+impl<T: Mul<Output=T>+Copy, const M: usize, const N: usize> Mul<T> for Matrix<T, M, N> { type Output = Self; fn mul(self, s: T) -> Self { self.map(|x| x*s) } }
+macro_rules! impl_scalar_mul { ($($T:ident)+) => {$(
+	impl<const M: usize, const N: usize> Mul<Matrix<$T, M, N>> for $T { type Output = Matrix<$T, M, N>; fn mul(self, m: Matrix<$T, M, N>) -> Self::Output { m.map(|x| self*x) } }
+)+} }
+impl_scalar_mul!{i32 u32 f32 f64}
+
+#[cfg(test)] mod test {
+	use crate::{xyz, xyzw, vec4, mat4, Matrix, mat3, mat4x4};
+	#[test] fn mul() {
+		let r: Matrix<f32, 2, 3> = Matrix([[1., 2., 3.], [4., 5., 6.]]);
+		assert_eq!((r * r.transpose()).0, [[14., 32.], [32., 77.]]); assert_eq!((r.transpose() * r).0, [[17., 22., 27.], [22., 29., 36.], [27., 36., 45.]]);
+		assert_eq!(r * [1., 1., 1.], [6., 15.]); assert_eq!(r.transpose().transpose(), r); assert_eq!(r.column(2), [3., 6.]);
+		let a: mat3 = Matrix([[1., 2., 3.], [0., 1., 4.], [5., 6., 0.]]);
+		assert_eq!(a * mat3::identity(), a); assert_eq!(mat3::identity() * a, a); assert_eq!(a * xyz{x: 1., y: 0., z: 0.}, xyz{x: 1., y: 0., z: 5.});
+		assert_eq!(2. * a, a + a); assert_eq!(a * 2., a + a); assert_eq!(a - a, num::zero()); assert_eq!((a * a).transpose(), a.transpose() * a.transpose());
+		let i: Matrix<i32, 2, 2> = Matrix::identity(); assert_eq!(3 * i, Matrix([[3, 0], [0, 3]])); assert_eq!(-i, Matrix([[-1, 0], [0, -1]]));
+	}
+	#[test] fn conversions() {
+		let m: mat4x4 = Matrix([[1., 2., 3., 4.], [5., 6., 7., 8.], [9., 10., 11., 12.], [13., 14., 15., 16.]]);
+		let v: mat4 = m.into(); assert_eq!(v.y, xyzw{x: 5., y: 6., z: 7., w: 8.}); assert_eq!(mat4x4::from(v), m);
+		assert_eq!(<[[f32; 4]; 4]>::from(m), m.0); assert_eq!(mat4x4::from(m.0), m);
+		assert_eq!(m * xyzw{x: 0., y: 0., z: 0., w: 1.}, vec4::from([4., 8., 12., 16.]));
+		let u = mat3::from(m); assert_eq!(u.0, [[1., 2., 3.], [5., 6., 7.], [9., 10., 11.]]);
+		assert_eq!(mat4x4::from(u).0, [[1., 2., 3., 0.], [5., 6., 7., 0.], [9., 10., 11., 0.], [0., 0., 0., 1.]]);
+		assert_eq!(mat3::from(xyz::<xyz<f32>>::from(u)), u); assert_eq!(bytemuck::bytes_of(&m).len(), 64);
+	}
+}
@@ -0,0 +1,161 @@
+use core::cmp::Ordering::{self, Less, Equal, Greater};
+use crate::{xy, vec2, MinMax, minmax, orient2d, iorient2d};
+
+// Vertices in order, the last connects back to the first
+#[derive(Clone, Copy, Debug)] pub struct Polygon<'t, T>(pub &'t [xy<T>]);
+// Vertices in order, open
+#[derive(Clone, Copy, Debug)] pub struct Polyline<'t, T>(pub &'t [xy<T>]);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)] pub enum Winding { Clockwise, CounterClockwise }
+
+fn within<T:PartialOrd+Copy>(a: xy<T>, b: xy<T>, p: xy<T>) -> bool { [(a.x, b.x, p.x), (a.y, b.y, p.y)].iter().all(|&(a, b, p)| if a < b { a <= p && p <= b } else { b <= p && p <= a }) }
+// Closed segments a b and c d share a point
+fn segments_intersect<T:PartialOrd+Copy>(orient: impl Fn(xy<T>, xy<T>, xy<T>)->Ordering, a: xy<T>, b: xy<T>, c: xy<T>, d: xy<T>) -> bool {
+	let (abc, abd, cda, cdb) = (orient(a, b, c), orient(a, b, d), orient(c, d, a), orient(c, d, b));
+	(abc != Equal && abd != Equal && abc != abd && cda != Equal && cdb != Equal && cda != cdb)
+		|| (abc == Equal && within(a, b, c)) || (abd == Equal && within(a, b, d)) || (cda == Equal && within(c, d, a)) || (cdb == Equal && within(c, d, b))
+}
+// Consecutive edges a b and b c overlap beyond b
+fn folds<T:PartialOrd+Copy>(orient: impl Fn(xy<T>, xy<T>, xy<T>)->Ordering, a: xy<T>, b: xy<T>, c: xy<T>) -> bool { a != b && b != c && orient(a, b, c) == Equal && (within(b, c, a) || within(a, b, c)) }
+// First pair of intersecting edges (edge i goes from vertex i to i+1), where consecutive edges only intersect when they fold back
+fn self_intersection<T:PartialOrd+Copy>(orient: impl Fn(xy<T>, xy<T>, xy<T>)->Ordering+Copy, points: &[xy<T>], closed: bool) -> Option<(usize, usize)> {
+	let n = points.len();
+	let edges = if closed && n > 2 { n } else { n.saturating_sub(1) };
+	let edge = |i: usize| (points[i], points[(i+1)%n]);
+	(0..edges).flat_map(|i| (i+1..edges).map(move |j| (i, j))).find(|&(i, j)| {
+		let ((a, b), (c, d)) = (edge(i), edge(j));
+		if j == i+1 { folds(orient, a, b, d) }
+		else if closed && i == 0 && j == edges-1 { folds(orient, c, a, b) }
+		else { segments_intersect(orient, a, b, c, d) }
+	})
+}
+
+macro_rules! impl_polygon { ($($T:ident $Wide:ident $Area:ident $orient:ident),+) => {$(
+impl Polygon<'_, $T> {
+	pub fn edges(self) -> impl Iterator<Item=(xy<$T>, xy<$T>)>+Clone { self.0.iter().copied().zip(self.0.iter().copied().cycle().skip(1)) }
+	// Relative to the first vertex (exact for integers)
+	fn twice_signed_area(self) -> $Wide {
+		let Some(&o) = self.0.first() else { return 0 as $Wide };
+		let o = o.map($Wide::from);
+		self.edges().map(|(a, b)| { let (a, b) = (a.map($Wide::from) - o, b.map($Wide::from) - o); a.x*b.y - a.y*b.x }).sum()
+	}
+	// Positive when counterclockwise (y up)
+	pub fn signed_area(self) -> $Area { self.twice_signed_area() as $Area / 2. }
+	pub fn area(self) -> $Area { self.signed_area().abs() }
+	pub fn winding(self) -> Option<Winding> { let a = self.twice_signed_area(); if a > 0 as $Wide { Some(Winding::CounterClockwise) } else if a < 0 as $Wide { Some(Winding::Clockwise) } else { None } }
+	// Area centroid, None when the area is zero
+	pub fn centroid(self) -> Option<vec2> {
+		let twice_area = self.twice_signed_area();
+		if twice_area == 0 as $Wide { return None }
+		let o = self.0[0].map($Wide::from);
+		let sum = self.edges().map(|(a, b)| { let (a, b) = (a.map($Wide::from) - o, b.map($Wide::from) - o); let cross = a.x*b.y - a.y*b.x; (a + b).map(|c| c*cross) }).fold(xy{x: 0 as $Wide, y: 0 as $Wide}, |s, t| s + t);
+		Some(o.map(|c| c as f32) + sum.map(|c| (c as f64 / (3. * twice_area as f64)) as f32))
+	}
+	pub fn perimeter(self) -> $Area { self.edges().map(|(a, b)| crate::norm((b.map($Wide::from) - a.map($Wide::from)).map(|c| c as $Area))).sum() }
+	pub fn bounds(self) -> Option<MinMax<xy<$T>>> { minmax(self.0.iter().copied()) }
+	fn orient(a: xy<$T>, b: xy<$T>, c: xy<$T>) -> Ordering { $orient(a, b, c).partial_cmp(&Default::default()).unwrap_or(Equal) }
+	// Strictly convex or with collinear vertices, in either winding, and not self intersecting
+	pub fn is_convex(self) -> bool {
+		let edges = || self.edges().filter(|(a, b)| a != b);
+		let turns = || edges().zip(edges().cycle().skip(1)).map(|((a, b), (_, c))| (a, b, c, Self::orient(a, b, c)));
+		let Some(turn) = turns().map(|(.., turn)| turn).find(|&turn| turn != Equal) else { return false };
+		if turns().any(|(a, b, c, t)| t == turn.reverse() || folds(Self::orient, a, b, c)) { return false }
+		// Winds only once: each coordinate changes direction at most twice
+		let changes = |c: fn(xy<$T>)->$T| { let signs = || edges().filter_map(|(a, b)| c(a).partial_cmp(&c(b))).filter(|&o| o != Equal); signs().zip(signs().cycle().skip(1)).filter(|(a, b)| a != b).count() };
+		changes(|p| p.x) <= 2 && changes(|p| p.y) <= 2
+	}
+	// Nonzero rule: number of counterclockwise turns around p (Sunday's crossing test with exact orientation)
+	pub fn winding_number(self, p: xy<$T>) -> i32 {
+		self.edges().map(|(a, b)| if a.y <= p.y { (b.y > p.y && Self::orient(a, b, p) == Greater) as i32 } else { -((b.y <= p.y && Self::orient(a, b, p) == Less) as i32) }).sum()
+	}
+	pub fn contains_nonzero(self, p: xy<$T>) -> bool { self.winding_number(p) != 0 }
+	pub fn contains_even_odd(self, p: xy<$T>) -> bool { self.winding_number(p) % 2 != 0 }
+	// First pair of intersecting edges (edge i goes from vertex i to i+1)
+	pub fn self_intersection(self) -> Option<(usize, usize)> { self_intersection(Self::orient, self.0, true) }
+	pub fn is_simple(self) -> bool { self.self_intersection().is_none() }
+}
+impl Polyline<'_, $T> {
+	pub fn edges(self) -> impl Iterator<Item=(xy<$T>, xy<$T>)>+Clone { self.0.windows(2).map(|w| (w[0], w[1])) }
+	pub fn length(self) -> $Area { self.edges().map(|(a, b)| crate::norm((b.map($Wide::from) - a.map($Wide::from)).map(|c| c as $Area))).sum() }
+	pub fn bounds(self) -> Option<MinMax<xy<$T>>> { minmax(self.0.iter().copied()) }
+	pub fn self_intersection(self) -> Option<(usize, usize)> { self_intersection(Polygon::<$T>::orient, self.0, false) }
+	pub fn is_simple(self) -> bool { self.self_intersection().is_none() }
+}
+)+} }
+impl_polygon!{f32 f64 f32 orient2d, i32 i128 f64 iorient2d}
+
+#[cfg(test)] mod test {
+	use crate::{xy, vec2, int2, MinMax, Polygon, Polyline, Winding};
+	fn p(x: f32, y: f32) -> vec2 { xy{x, y} }
+	fn i(x: i32, y: i32) -> int2 { xy{x, y} }
+	#[test] fn area() {
+		let square = [p(0., 0.), p(2., 0.), p(2., 2.), p(0., 2.)];
+		let s = Polygon(&square);
+		assert_eq!(s.signed_area(), 4.); assert_eq!(s.winding(), Some(Winding::CounterClockwise)); assert_eq!(s.perimeter(), 8.);
+		assert_eq!(s.bounds(), Some(MinMax{min: p(0., 0.), max: p(2., 2.)}));
+		let mut cw = square; cw.reverse(); let cw = Polygon(&cw);
+		assert_eq!(cw.signed_area(), -4.); assert_eq!(cw.area(), 4.); assert_eq!(cw.winding(), Some(Winding::Clockwise));
+		assert_eq!(s.winding_number(p(1., 1.)), 1); assert_eq!(cw.winding_number(p(1., 1.)), -1); assert_eq!(s.winding_number(p(3., 1.)), 0);
+		// Bowtie: opposite lobes cancel
+		let bowtie = Polygon(&[p(0., 0.), p(2., 2.), p(2., 0.), p(0., 2.)]);
+		assert_eq!(bowtie.signed_area(), 0.); assert_eq!(bowtie.winding(), None); assert_eq!(bowtie.centroid(), None);
+		assert_eq!(Polygon::<f32>(&[]).signed_area(), 0.); assert!(Polygon::<f32>(&[]).bounds().is_none());
+		// Exact in i128 across the whole range
+		let (m, n) = (i32::MAX, i32::MIN);
+		let big = Polygon(&[i(n, n), i(m, n), i(m, m), i(n, m)]);
+		assert_eq!(big.signed_area(), (u32::MAX as f64).powi(2)); assert_eq!(big.perimeter(), 4.*u32::MAX as f64);
+		assert!(big.contains_nonzero(i(0, 0))); assert_eq!(big.centroid(), Some(p(0., 0.)));
+	}
+	#[test] fn centroid() {
+		assert_eq!(Polygon(&[p(0., 0.), p(2., 0.), p(2., 2.), p(0., 2.)]).centroid(), Some(p(1., 1.)));
+		assert_eq!(Polygon(&[p(0., 2.), p(2., 2.), p(2., 0.), p(0., 0.)]).centroid(), Some(p(1., 1.)));
+		assert_eq!(Polygon(&[i(0, 0), i(4, 0), i(0, 4)]).centroid(), Some(p(4./3., 4./3.)));
+		// L shape: weighted by area, not the vertex average
+		let c = Polygon(&[p(0., 0.), p(2., 0.), p(2., 1.), p(1., 1.), p(1., 2.), p(0., 2.)]).centroid().unwrap();
+		assert!((c - p(5./6., 5./6.)).map(f32::abs).max_element() < 1e-6);
+		// Far from the origin
+		assert_eq!(Polygon(&[i(1<<30, 1<<30), i((1<<30)+6, 1<<30), i(1<<30, (1<<30)+6)]).centroid(), Some(p((1<<30) as f32 + 2., (1<<30) as f32 + 2.)));
+	}
+	#[test] fn convex() {
+		let square = [p(0., 0.), p(2., 0.), p(2., 2.), p(0., 2.)];
+		assert!(Polygon(&square).is_convex()); let mut cw = square; cw.reverse(); assert!(Polygon(&cw).is_convex());
+		assert!(!Polygon(&[p(0., 0.), p(2., 0.), p(2., 1.), p(1., 1.), p(1., 2.), p(0., 2.)]).is_convex());
+		// A collinear vertex stays convex, folding back does not
+		assert!(Polygon(&[p(0., 0.), p(1., 0.), p(2., 0.), p(2., 2.)]).is_convex());
+		assert!(!Polygon(&[p(0., 0.), p(2., 0.), p(1., 0.), p(2., 2.)]).is_convex());
+		assert!(Polygon(&[p(0., 0.), p(1., 0.), p(1., 0.), p(0., 1.)]).is_convex()); // Repeated vertex
+		assert!(!Polygon(&[p(0., 0.), p(1., 0.), p(2., 0.)]).is_convex()); // No turn
+		assert!(!Polygon::<f32>(&[]).is_convex());
+		assert!(Polygon(&[i(i32::MIN, i32::MIN), i(i32::MAX, i32::MIN), i(i32::MAX, i32::MAX)]).is_convex());
+	}
+	// Regular pentagon (rounded, counterclockwise), the pentagram takes every other vertex
+	const PENTAGON: [(i32, i32); 5] = [(0, 100), (-95, 31), (-59, -81), (59, -81), (95, 31)];
+	#[test] fn pentagram() {
+		let star = [0, 2, 4, 1, 3].map(|k| i(PENTAGON[k].0, PENTAGON[k].1));
+		let fstar = star.map(|p| p.map(|c| c as f32));
+		for (star, winding) in [(star, 1), ({ let mut s = star; s.reverse(); s }, -1)] {
+			let star = Polygon(&star);
+			// Turns consistently but winds twice around the center
+			assert!(!star.is_convex()); assert!(!star.is_simple());
+			assert_eq!(star.winding_number(i(0, 0)), 2*winding); assert!(star.contains_nonzero(i(0, 0))); assert!(!star.contains_even_odd(i(0, 0)));
+			assert_eq!(star.winding_number(i(0, 70)), winding); assert!(star.contains_even_odd(i(0, 70))); // Tip
+			assert_eq!(star.winding_number(i(0, -70)), 0); assert_eq!(star.winding_number(i(200, 0)), 0); // Between tips and outside
+		}
+		let fstar = Polygon(&fstar);
+		assert_eq!(fstar.winding_number(p(0., 0.)), 2); assert_eq!(fstar.winding_number(p(0., 70.)), 1); assert_eq!(fstar.winding_number(p(0., -70.)), 0);
+		assert_eq!(fstar.winding(), Some(Winding::CounterClockwise));
+	}
+	#[test] fn self_intersection() {
+		assert!(Polygon(&[p(0., 0.), p(1., 0.), p(0., 1.)]).is_simple());
+		assert_eq!(Polygon(&[p(0., 0.), p(2., 2.), p(2., 0.), p(0., 2.)]).self_intersection(), Some((0, 2)));
+		assert_eq!(Polygon(&[p(0., 0.), p(2., 0.), p(1., 0.), p(2., 2.)]).self_intersection(), Some((0, 1)));
+		assert!(!Polygon(&[p(0., 0.), p(2., 0.), p(1., 1.), p(2., 2.), p(0., 2.), p(1., 1.)]).is_simple()); // Touching vertex
+		let z = Polyline(&[p(0., 0.), p(3., 0.), p(0., 4.), p(3., 4.)]);
+		assert_eq!(z.length(), 11.); assert!(z.is_simple());
+		assert_eq!(Polyline(&[p(0., 0.), p(3., 0.), p(3., 3.), p(1., -1.)]).self_intersection(), Some((0, 2)));
+		// Closing point equal to the start is an intersection only for open polylines
+		assert_eq!(Polyline(&[p(0., 0.), p(1., 0.), p(0., 1.), p(0., 0.)]).self_intersection(), Some((0, 2)));
+		assert_eq!(Polyline(&[i(0, 0), i(5, 0), i(5, 5), i(2, 0)]).self_intersection(), Some((0, 2)));
+		assert!(Polyline::<f32>(&[]).is_simple()); assert_eq!(Polyline::<f32>(&[]).length(), 0.);
+	}
+}
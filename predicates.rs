@@ -0,0 +1,206 @@
+// Shewchuk, Adaptive Precision Floating-Point Arithmetic and Fast Robust Geometric Predicates (1997)
+// f32 coordinates are evaluated in f64 with a forward error bound, and exactly with expansions only when the sign is uncertain.
+// Exact terms of f32 coordinates (up to degree 5) neither overflow nor underflow in f64.
+// The returned determinant has the exact sign (0 iff degenerate), its magnitude is approximate.
+use core::cmp::Ordering;
+use crate::{xy, xyz, vec2, vec3, int2};
+
+const EPSILON: f64 = f64::EPSILON/2.;
+const SPLITTER: f64 = 134217729.; // 2^27+1
+
+fn two_sum(a: f64, b: f64) -> (f64, f64) { let x = a + b; let bv = x - a; let av = x - bv; (x, (a - av) + (b - bv)) }
+fn split(a: f64) -> (f64, f64) { let c = SPLITTER*a; let hi = c - (c - a); (hi, a - hi) }
+fn two_product(a: f64, b: f64) -> [f64; 2] { let x = a*b; let ((ah, al), (bh, bl)) = (split(a), split(b)); [al*bl - (((x - ah*bh) - al*bh) - ah*bl), x] }
+
+// Nonoverlapping components by increasing magnitude, without zeros (except a single zero component)
+#[derive(Clone, Copy)] struct Expansion<const N: usize> { len: usize, c: [f64; N] }
+impl<const N: usize> Expansion<N> {
+	fn components(&self) -> &[f64] { &self.c[..self.len] }
+	fn push(&mut self, x: f64) { if x != 0. { self.c[self.len] = x; self.len += 1; } }
+	fn finish(mut self, q: f64) -> Self { if q != 0. || self.len == 0 { self.c[self.len] = q; self.len += 1; } self }
+	fn new() -> Self { Self{len: 0, c: [0.; N]} }
+	fn neg(mut self) -> Self { for c in &mut self.c[..self.len] { *c = -*c; } self }
+	fn value(&self) -> f64 { self.components().iter().sum() }
+	fn sum(e: &[f64], f: &[f64]) -> Self { let mut h = Self::new(); let q = merge(e, f, |y| h.push(y)); h.finish(q) }
+	fn scale(e: &[f64], b: f64) -> Self {
+		let mut h = Self::new();
+		let [lo, mut q] = two_product(e[0], b);
+		h.push(lo);
+		for &c in &e[1..] {
+			let [lo, hi] = two_product(c, b);
+			let (s, y) = two_sum(q, lo); h.push(y);
+			let (x, y) = two_sum(hi, s); h.push(y);
+			q = x;
+		}
+		h.finish(q)
+	}
+	fn product(e: &[f64], f: &[f64]) -> Self { f.iter().fold(Self::new().finish(0.), |sum, &b| Self::sum(sum.components(), Self::scale(e, b).components())) }
+}
+
+// Merges by magnitude and accumulates with Two-Sum (fast expansion sum): emits the components by increasing magnitude and returns the largest
+fn merge(e: &[f64], f: &[f64], mut emit: impl FnMut(f64)) -> f64 {
+	let (mut i, mut j) = (0, 0);
+	let mut next = || if j >= f.len() || (i < e.len() && e[i].abs() < f[j].abs()) { i += 1; e[i-1] } else { j += 1; f[j-1] };
+	if e.len() + f.len() == 0 { return 0. }
+	let mut q = next();
+	for _ in 1..e.len()+f.len() { let (x, y) = two_sum(q, next()); emit(y); q = x; }
+	q
+}
+
+fn square(x: f64) -> [f64; 2] { two_product(x, x) }
+fn cross(a: xy<f64>, b: xy<f64>) -> Expansion<4> { Expansion::sum(&two_product(a.x, b.y), &two_product(-a.y, b.x)) }
+// |a 1; b 1; c 1| = (b-a)×(c-a)
+fn triangle(a: xy<f64>, b: xy<f64>, c: xy<f64>) -> Expansion<12> { Expansion::sum(Expansion::<8>::sum(cross(a, b).components(), cross(b, c).components()).components(), cross(c, a).components()) }
+// |p l 1| (rows p, lifts l) expanded along the lift column
+fn lifted<const L: usize>([a, b, c, d]: [xy<f64>; 4], [la, lb, lc, ld]: [Expansion<L>; 4]) -> Expansion<384> {
+	let term = |l: Expansion<L>, t: Expansion<12>| Expansion::<96>::product(t.components(), l.components());
+	let ab = Expansion::<192>::sum(term(la, triangle(b, c, d)).components(), term(lb, triangle(a, c, d)).neg().components());
+	let cd = Expansion::<192>::sum(term(lc, triangle(a, b, d)).components(), term(ld, triangle(a, b, c)).neg().components());
+	Expansion::sum(ab.components(), cd.components())
+}
+fn orient3d_exact(p: [xyz<f64>; 4]) -> Expansion<384> { lifted(p.map(|p| p.xy()), p.map(|p| Expansion::<1>::new().finish(p.z))) }
+
+// Positive when a, b, c are counterclockwise
+pub fn orient2d(a: vec2, b: vec2, c: vec2) -> f64 {
+	let [a, b, c] = [a, b, c].map(|p| p.map(f64::from));
+	let (left, right) = ((a.x - c.x) * (b.y - c.y), (a.y - c.y) * (b.x - c.x));
+	let det = left - right;
+	if det.abs() >= (3. + 16.*EPSILON)*EPSILON * (left.abs() + right.abs()) { return det }
+	triangle(a, b, c).value()
+}
+
+// Positive when d is below the plane of a, b, c (which appear counterclockwise from above): |a-d; b-d; c-d|
+pub fn orient3d(a: vec3, b: vec3, c: vec3, d: vec3) -> f64 {
+	let p = [a, b, c, d].map(|p| p.map(f64::from));
+	let [a, b, c] = [p[0], p[1], p[2]].map(|v| v - p[3]);
+	let (bxcy, cxby, cxay, axcy, axby, bxay) = (b.x*c.y, c.x*b.y, c.x*a.y, a.x*c.y, a.x*b.y, b.x*a.y);
+	let det = a.z*(bxcy - cxby) + b.z*(cxay - axcy) + c.z*(axby - bxay);
+	let permanent = (bxcy.abs() + cxby.abs())*a.z.abs() + (cxay.abs() + axcy.abs())*b.z.abs() + (axby.abs() + bxay.abs())*c.z.abs();
+	if det.abs() >= (7. + 56.*EPSILON)*EPSILON * permanent { return det }
+	orient3d_exact(p).value()
+}
+
+// Positive when d is inside the circle through a, b, c (counterclockwise)
+pub fn incircle(a: vec2, b: vec2, c: vec2, d: vec2) -> f64 {
+	let p = [a, b, c, d].map(|p| p.map(f64::from));
+	let [a, b, c] = [p[0], p[1], p[2]].map(|v| v - p[3]);
+	let (bxcy, cxby, cxay, axcy, axby, bxay) = (b.x*c.y, c.x*b.y, c.x*a.y, a.x*c.y, a.x*b.y, b.x*a.y);
+	let [la, lb, lc] = [a, b, c].map(|p| p.x*p.x + p.y*p.y);
+	let det = la*(bxcy - cxby) + lb*(cxay - axcy) + lc*(axby - bxay);
+	let permanent = (bxcy.abs() + cxby.abs())*la + (cxay.abs() + axcy.abs())*lb + (axby.abs() + bxay.abs())*lc;
+	if det.abs() >= (10. + 96.*EPSILON)*EPSILON * permanent { return det }
+	lifted(p, p.map(|p| Expansion::<4>::sum(&square(p.x), &square(p.y)))).value()
+}
+
+// Positive when e is inside the sphere through a, b, c, d (with orient3d(a, b, c, d) > 0)
+pub fn insphere(a: vec3, b: vec3, c: vec3, d: vec3, e: vec3) -> f64 {
+	let p = [a, b, c, d, e].map(|p| p.map(f64::from));
+	let [a, b, c, d] = [p[0], p[1], p[2], p[3]].map(|v| v - p[4]);
+	let (axby, bxay, bxcy, cxby, cxdy, dxcy, dxay, axdy, axcy, cxay, bxdy, dxby) = (a.x*b.y, b.x*a.y, b.x*c.y, c.x*b.y, c.x*d.y, d.x*c.y, d.x*a.y, a.x*d.y, a.x*c.y, c.x*a.y, b.x*d.y, d.x*b.y);
+	let (ab, bc, cd, da, ac, bd) = (axby - bxay, bxcy - cxby, cxdy - dxcy, dxay - axdy, axcy - cxay, bxdy - dxby);
+	let abc = a.z*bc - b.z*ac + c.z*ab;
+	let bcd = b.z*cd - c.z*bd + d.z*bc;
+	let cda = c.z*da + d.z*ac + a.z*cd;
+	let dab = d.z*ab + a.z*bd + b.z*da;
+	let [la, lb, lc, ld] = [a, b, c, d].map(|p| p.x*p.x + p.y*p.y + p.z*p.z);
+	let det = (ld*abc - lc*dab) + (lb*cda - la*bcd);
+	let [az, bz, cz, dz] = [a, b, c, d].map(|p| p.z.abs());
+	let (ab, bc, cd, da, ac, bd) = (axby.abs() + bxay.abs(), bxcy.abs() + cxby.abs(), cxdy.abs() + dxcy.abs(), dxay.abs() + axdy.abs(), axcy.abs() + cxay.abs(), bxdy.abs() + dxby.abs());
+	let permanent = (cd*bz + bd*cz + bc*dz)*la + (da*cz + ac*dz + cd*az)*lb + (ab*dz + bd*az + da*bz)*lc + (bc*az + ac*bz + ab*cz)*ld;
+	if det.abs() >= (16. + 224.*EPSILON)*EPSILON * permanent { return det }
+	// |p l 1| expanded along the lift column, summed in stages without storing the last sum (up to 5760 components): the fallback needs about 160 KiB of stack (220 KiB unoptimized)
+	let [a, b, c, d, e] = p;
+	let lift = |p: xyz<f64>| Expansion::<6>::sum(Expansion::<4>::sum(&square(p.x), &square(p.y)).components(), &square(p.z));
+	let term = |negate: bool, p: xyz<f64>, o: [xyz<f64>; 4]| Expansion::<1152>::product(orient3d_exact(o).components(), if negate { lift(p).neg() } else { lift(p) }.components());
+	let ab = Expansion::<2304>::sum(term(true, a, [b, c, d, e]).components(), term(false, b, [a, c, d, e]).components());
+	let cd = Expansion::<2304>::sum(term(true, c, [a, b, d, e]).components(), term(false, d, [a, b, c, e]).components());
+	let abe = Expansion::<3456>::sum(ab.components(), term(true, e, [a, b, c, d]).components());
+	let mut value = 0.; let q = merge(abe.components(), cd.components(), |y| value += y); value + q
+}
+
+// Exact with widened integers
+pub fn iorient2d(a: int2, b: int2, c: int2) -> i128 {
+	let c = c.map(i128::from); let [a, b] = [a, b].map(|p| p.map(i128::from) - c);
+	a.x*b.y - a.y*b.x
+}
+// The determinant needs up to 134 bits: terms are split into 32 bit halves of the cross products
+pub fn iincircle(a: int2, b: int2, c: int2, d: int2) -> Ordering {
+	let d = d.map(i128::from); let [a, b, c] = [a, b, c].map(|p| p.map(i128::from) - d);
+	let cross = |a: xy<i128>, b: xy<i128>| a.x*b.y - a.y*b.x;
+	let lift = |p: xy<i128>| p.x*p.x + p.y*p.y;
+	let (hi, lo) = [(a, cross(b, c)), (b, cross(c, a)), (c, cross(a, b))].iter().fold((0, 0), |(hi, lo), &(p, cross)| (hi + lift(p)*(cross >> 32), lo + lift(p)*(cross & 0xFFFF_FFFF)));
+	let (hi, lo) = (hi + (lo >> 32), lo & 0xFFFF_FFFF); // 0 <= lo < 2^32
+	if hi != 0 { hi.cmp(&0) } else { lo.cmp(&0) }
+}
+
+// Orientation sign for generic code over vec2 and int2 (Greater when counterclockwise)
+pub trait Orient2d: Sized { fn orient2d(a: xy<Self>, b: xy<Self>, c: xy<Self>) -> Ordering; }
+impl Orient2d for f32 { fn orient2d(a: vec2, b: vec2, c: vec2) -> Ordering { orient2d(a, b, c).partial_cmp(&0.).unwrap_or(Ordering::Equal) } }
+impl Orient2d for i32 { fn orient2d(a: int2, b: int2, c: int2) -> Ordering { iorient2d(a, b, c).cmp(&0) } }
+
+#[cfg(test)] mod test {
+	use core::cmp::Ordering;
+	use crate::{xy, xyz, vec2, vec3, int2, orient2d, orient3d, incircle, insphere, iorient2d, iincircle};
+	#[allow(non_camel_case_types)] type int3 = xyz<i32>;
+	fn sign(x: f64) -> Ordering { x.partial_cmp(&0.).unwrap() }
+	fn f2(p: int2) -> vec2 { p.map(|x| x as f32) }
+	fn f3(p: int3) -> vec3 { p.map(|x| x as f32) }
+	fn det3([a, b, c]: [xyz<i128>; 3]) -> i128 { a.x*(b.y*c.z - b.z*c.y) - a.y*(b.x*c.z - b.z*c.x) + a.z*(b.x*c.y - b.y*c.x) }
+	fn iorient3d(a: int3, b: int3, c: int3, d: int3) -> Ordering { let d = d.map(i128::from); det3([a, b, c].map(|p| p.map(i128::from) - d)).cmp(&0) }
+	// |p-e |p-e|²| expanded along the lift column (differences are small enough for i128)
+	fn iinsphere(a: int3, b: int3, c: int3, d: int3, e: int3) -> Ordering {
+		let e = e.map(i128::from); let p = [a, b, c, d].map(|p| p.map(i128::from) - e);
+		let minor = |i: usize| { let mut rows = p.iter().enumerate().filter(|&(j, _)| j != i).map(|(_, &p)| p); [(); 3].map(|_| rows.next().unwrap()) };
+		(0..4).map(|i| { let p = p[i]; (if i%2 == 0 { -1 } else { 1 }) * (p.x*p.x + p.y*p.y + p.z*p.z) * det3(minor(i)) }).sum::<i128>().cmp(&0)
+	}
+	fn check(a: int3, b: int3, c: int3, d: int3, e: int3) {
+		let [a2, b2, c2, d2] = [a, b, c, d].map(|p| xy{x: p.x, y: p.y});
+		assert_eq!(sign(orient2d(f2(a2), f2(b2), f2(c2))), iorient2d(a2, b2, c2).cmp(&0));
+		assert_eq!(sign(incircle(f2(a2), f2(b2), f2(c2), f2(d2))), iincircle(a2, b2, c2, d2));
+		assert_eq!(sign(orient3d(f3(a), f3(b), f3(c), f3(d))), iorient3d(a, b, c, d));
+		assert_eq!(sign(insphere(f3(a), f3(b), f3(c), f3(d), f3(e))), iinsphere(a, b, c, d, e));
+	}
+	const OFFSET: int3 = xyz{x: 4_000_001, y: -3_000_017, z: 2_500_003}; // f32 products of the differences are inexact
+	#[test] fn degenerate() {
+		let p = |x, y, z| OFFSET + xyz{x, y, z};
+		// Collinear
+		let [a, b, c] = [p(-7, 3, 1), p(-1, 5, 3), p(8, 8, 6)];
+		assert_eq!(orient2d(f2(a.xy()), f2(b.xy()), f2(c.xy())), 0.);
+		assert_eq!(orient3d(f3(a), f3(b), f3(c), f3(p(2, -9, 4))), 0.);
+		// Cocircular (x²+y²=25), coplanar and cospherical (x²+y²+z²=25)
+		let [a, b, c, d, e] = [p(3, 4, 0), p(-4, 0, 3), p(0, -3, 4), p(0, 0, -5), p(5, 0, 0)];
+		assert_eq!(iorient3d(a, b, c, d), Ordering::Greater);
+		assert_eq!(incircle(f2(a.xy()), f2(p(-4, 3, 0).xy()), f2(p(0, -5, 0).xy()), f2(e.xy())), 0.);
+		assert_eq!(orient3d(f3(a), f3(e), f3(p(-3, -4, 0)), f3(p(1, 2, 0))), 0.);
+		assert_eq!(insphere(f3(a), f3(b), f3(c), f3(d), f3(e)), 0.);
+		check(a, b, c, d, e);
+		// Inside and outside by one unit
+		assert!(incircle(f2(a.xy()), f2(p(-4, 3, 0).xy()), f2(p(0, -5, 0).xy()), f2(p(4, 0, 0).xy())) > 0.);
+		assert!(incircle(f2(a.xy()), f2(p(-4, 3, 0).xy()), f2(p(0, -5, 0).xy()), f2(p(6, 0, 0).xy())) < 0.);
+		assert!(insphere(f3(a), f3(b), f3(c), f3(d), f3(p(4, 0, 0))) > 0.);
+		assert!(insphere(f3(a), f3(b), f3(c), f3(d), f3(p(6, 0, 0))) < 0.);
+	}
+	#[test] fn near_degenerate() {
+		let mut seed = 1u32; let mut random = |n: u32| { seed = seed.wrapping_mul(1103515245).wrapping_add(12345); ((seed >> 8) % (2*n+1)) as i32 - n as i32 };
+		// Integer points on the sphere of radius 15 (scaled so that products of the differences are inexact in f64)
+		let mut sphere = [xyz{x: 0, y: 0, z: 0}; 150]; let mut len = 0;
+		for x in -15..=15 { for y in -15..=15 { for z in -15..=15 { if x*x + y*y + z*z == 225 { sphere[len] = xyz{x, y, z}; len += 1; } } } }
+		assert_eq!(len, sphere.len());
+		for _ in 0..1000 {
+			let offset = OFFSET + xyz{x: random(1<<20), y: random(1<<20), z: random(1<<20)};
+			let [a, b, c, d, e] = [(); 5].map(|_| offset + sphere[(random(75) + 75) as usize % 150].map(|x| x << 14));
+			let [dd, de] = [(); 2].map(|_| xyz{x: random(1), y: random(1), z: random(1)});
+			check(a, b, c, d + dd, e + de);
+			// Along a line and a plane (with a small normal so that perturbations change the determinants by less than the rounding errors)
+			let [u, v] = [(); 2].map(|_| xyz{x: random(9), y: random(9), z: random(9)});
+			let [s, t] = [(); 2].map(|_| random(1<<17));
+			let [dc, dd, de] = [(); 3].map(|_| xyz{x: random(1), y: random(1), z: random(1)});
+			check(offset, offset + u, offset + u.map(|x| x*s) + dc, offset + u.map(|x| x*t) + v.map(|x| x*s) + dd, offset + v.map(|x| x*t) + de);
+			// Near the origin (scaled by 2^-60) against a line through it: (b-a)×(c-a) = a×(b-c) since b×c = 0
+			let (w, k, j) = (xy{x: random(9), y: random(9)}, random(1<<17), random(1<<17));
+			let [a, b, c] = [xy{x: random(1<<20), y: random(1<<20)}, w.map(|x| x*k), w.map(|x| x*(k+j))];
+			let bc = b - c;
+			assert_eq!(sign(orient2d(a.map(|x| x as f32/(1u64<<60) as f32), f2(b), f2(c))), (i64::from(a.x)*i64::from(bc.y) - i64::from(a.y)*i64::from(bc.x)).cmp(&0));
+		}
+	}
+}
@@ -0,0 +1,119 @@
+use core::ops::{Mul,MulAssign,Neg};
+use crate::{xyzw, vec3, vec4, mat3, mat4x4, Matrix, cross};
+#[allow(unused_imports)] use crate::Float;
+
+// xyz: vector part, w: scalar part
+#[repr(C)] #[derive(Clone, Copy, Debug, PartialEq)] pub struct Quaternion(pub vec4);
+#[allow(non_camel_case_types)] pub type quat = Quaternion;
+unsafe impl bytemuck::Zeroable for Quaternion {}
+unsafe impl bytemuck::Pod for Quaternion {}
+impl Default for Quaternion { fn default() -> Self { Self::IDENTITY } }
+
+impl Quaternion {
+	pub const IDENTITY: Self = Quaternion(xyzw{x: 0., y: 0., z: 0., w: 1.});
+	pub fn new(v: vec3, w: f32) -> Self { Quaternion(v.xyz0().with_w(w)) }
+	pub fn vector(self) -> vec3 { self.0.xyz() }
+	pub fn scalar(self) -> f32 { self.0.w }
+	pub fn conjugate(self) -> Self { Self::new(-self.vector(), self.scalar()) }
+	pub fn dot(self, b: Self) -> f32 { crate::dot(self.0, b.0) }
+	// None for the zero quaternion. Unit quaternions are inverted by conjugate
+	pub fn inverse(self) -> Option<Self> { let sq = self.dot(self); (sq != 0.).then(|| { let Quaternion(q) = self.conjugate(); Quaternion(q/sq) }) }
+	pub fn rotate(self, v: vec3) -> vec3 { let (u, w) = (self.vector(), self.scalar()); let t = 2.*cross(u, v); v + w*t + cross(u, t) }
+}
+impl Quaternion {
+	pub fn norm(self) -> f32 { self.dot(self).sqrt() }
+	pub fn normalize(self) -> Self { Quaternion(self.0/self.norm()) }
+	// Right handed rotation of angle radians around axis
+	pub fn from_axis_angle(axis: vec3, angle: f32) -> Self { let (s, c) = f32::sin_cos(angle/2.); Self::new(s*crate::normalize(axis), c) }
+	pub fn to_axis_angle(self) -> (vec3, f32) { let w = self.scalar().clamp(-1., 1.); let s = (1.-w*w).sqrt(); (if s > f32::EPSILON { self.vector()/s } else { crate::xyz{x: 1., y: 0., z: 0.} }, 2.*w.acos()) }
+	// Rotates around x (roll) then y (pitch) then z (yaw)
+	pub fn from_euler(roll: f32, pitch: f32, yaw: f32) -> Self {
+		let [(sx, cx), (sy, cy), (sz, cz)] = [roll, pitch, yaw].map(|a| f32::sin_cos(a/2.));
+		Quaternion(xyzw{x: sx*cy*cz - cx*sy*sz, y: cx*sy*cz + sx*cy*sz, z: cx*cy*sz - sx*sy*cz, w: cx*cy*cz + sx*sy*sz})
+	}
+	pub fn to_euler(self) -> (f32, f32, f32) {
+		let xyzw{x,y,z,w} = self.0;
+		((2.*(w*x + y*z)).atan2(1. - 2.*(x*x + y*y)), (2.*(w*y - z*x)).clamp(-1., 1.).asin(), (2.*(w*z + x*y)).atan2(1. - 2.*(y*y + z*z)))
+	}
+}
+
+impl Mul for Quaternion { type Output = Self; fn mul(self, b: Self) -> Self { // Hamilton product: rotates by b then self
+	let (u, w, v, s) = (self.vector(), self.scalar(), b.vector(), b.scalar());
+	Self::new(w*v + s*u + cross(u, v), w*s - crate::dot(u, v))
+} }
+impl MulAssign for Quaternion { fn mul_assign(&mut self, b: Self) { *self = *self * b } }
+impl Mul<vec3> for Quaternion { type Output = vec3; fn mul(self, v: vec3) -> vec3 { self.rotate(v) } }
+impl Neg for Quaternion { type Output = Self; fn neg(self) -> Self { Quaternion(-self.0) } } // Same rotation
+
+impl From<Quaternion> for mat3 { fn from(q: Quaternion) -> Self {
+	let xyzw{x,y,z,w} = q.0;
+	Matrix([
+		[1. - 2.*(y*y + z*z), 2.*(x*y - w*z), 2.*(x*z + w*y)],
+		[2.*(x*y + w*z), 1. - 2.*(x*x + z*z), 2.*(y*z - w*x)],
+		[2.*(x*z - w*y), 2.*(y*z + w*x), 1. - 2.*(x*x + y*y)]
+	])
+} }
+impl From<Quaternion> for mat4x4 { fn from(q: Quaternion) -> Self { let Matrix([r0,r1,r2]) = mat3::from(q); let [a,b,c] = [r0,r1,r2].map(|[x,y,z]| [x,y,z,0.]); Matrix([a,b,c,[0.,0.,0.,1.]]) } }
+// Rotation part of an orthonormal matrix
+impl From<mat3> for Quaternion { fn from(Matrix(m): mat3) -> Self {
+	let [[m00,m01,m02],[m10,m11,m12],[m20,m21,m22]] = m;
+	let trace = m00 + m11 + m22;
+	// Largest diagonal term for stability
+	if trace > 0. { let s = 2.*(trace + 1.).sqrt(); Quaternion(xyzw{x: (m21 - m12)/s, y: (m02 - m20)/s, z: (m10 - m01)/s, w: s/4.}) }
+	else if m00 > m11 && m00 > m22 { let s = 2.*(1. + m00 - m11 - m22).sqrt(); Quaternion(xyzw{x: s/4., y: (m01 + m10)/s, z: (m02 + m20)/s, w: (m21 - m12)/s}) }
+	else if m11 > m22 { let s = 2.*(1. + m11 - m00 - m22).sqrt(); Quaternion(xyzw{x: (m01 + m10)/s, y: s/4., z: (m12 + m21)/s, w: (m02 - m20)/s}) }
+	else { let s = 2.*(1. + m22 - m00 - m11).sqrt(); Quaternion(xyzw{x: (m02 + m20)/s, y: (m12 + m21)/s, z: s/4., w: (m10 - m01)/s}) }
+} }
+impl From<mat4x4> for Quaternion { fn from(m: mat4x4) -> Self { Self::from(mat3::from(m)) } }
+
+// Shortest path
+pub fn nlerp(t: f32, a: Quaternion, b: Quaternion) -> Quaternion { let b = if a.dot(b) < 0. { -b } else { b }; Quaternion(num::lerp(t, a.0, b.0)).normalize() }
+pub fn slerp(t: f32, a: Quaternion, b: Quaternion) -> Quaternion {
+	let (b, cos) = { let cos = a.dot(b); if cos < 0. { (-b, -cos) } else { (b, cos) } };
+	if cos > 1. - f32::EPSILON { return nlerp(t, a, b); } // sin(angle) ~ 0
+	let angle = cos.acos();
+	let [sa, sb] = [(1.-t)*angle, t*angle].map(f32::sin);
+	Quaternion((sa*a.0 + sb*b.0)/angle.sin())
+}
+impl num::Lerp for Quaternion { fn lerp(t: f32, a: Self, b: Self) -> Self { nlerp(t, a, b) } }
+
+#[cfg(test)] mod test {
+	use core::f32::consts::{FRAC_PI_2, FRAC_PI_4};
+	use crate::{xyz, vec3, mat3, mat4x4, Quaternion, slerp};
+	fn close(a: vec3, b: vec3) -> bool { (a-b).into_iter().all(|e| e.abs() < 1e-5) }
+	fn same(a: Quaternion, b: Quaternion) -> bool { (a.dot(b).abs() - 1.).abs() < 1e-5 } // q and -q are the same rotation
+	const X: vec3 = xyz{x: 1., y: 0., z: 0.}; const Y: vec3 = xyz{x: 0., y: 1., z: 0.}; const Z: vec3 = xyz{x: 0., y: 0., z: 1.};
+	#[test] fn axis_angle() {
+		let q = Quaternion::from_axis_angle(2.*Z, FRAC_PI_2);
+		assert!(close(q*X, Y)); assert!(close(mat3::from(q)*X, Y)); assert!(close(mat3::rotation_z(FRAC_PI_2)*X, Y));
+		for (axis, angle) in [(Z, FRAC_PI_2), (xyz{x: 1., y: -2., z: 0.5}, 0.7), (Y, -2.5), (X, 3.)] {
+			let q = Quaternion::from_axis_angle(axis, angle); let (a, b) = q.to_axis_angle();
+			assert!(same(Quaternion::from_axis_angle(a, b), q)); assert!(close(b*a, angle*crate::normalize(axis))); // |angle| < π
+			assert!(same(Quaternion::from(mat3::from(q)), q)); assert!(same(Quaternion::from(mat4x4::from(q)), q));
+			assert!(close(mat3::rotation(axis, angle)*xyz{x: 0.3, y: -0.2, z: 0.5}, q*xyz{x: 0.3, y: -0.2, z: 0.5}));
+		}
+		let (axis, angle) = Quaternion::IDENTITY.to_axis_angle(); assert_eq!((axis, angle), (X, 0.));
+	}
+	#[test] fn euler() {
+		// Roll around x first, then pitch around y, then yaw around z
+		let q = Quaternion::from_euler(FRAC_PI_2, 0., FRAC_PI_2); assert!(close(q*X, Y)); assert!(close(q*Y, Z)); assert!(close(q*Z, X));
+		for (roll, pitch, yaw) in [(0.1, 0.2, 0.3), (-1., 0.5, 2.), (3., -1.2, -2.)] {
+			let q = Quaternion::from_euler(roll, pitch, yaw);
+			assert!(same(q, Quaternion::from_axis_angle(Z, yaw)*Quaternion::from_axis_angle(Y, pitch)*Quaternion::from_axis_angle(X, roll)));
+			let (roll, pitch, yaw) = q.to_euler(); assert!(same(Quaternion::from_euler(roll, pitch, yaw), q));
+		}
+	}
+	#[test] fn composition() {
+		let (q, p) = (Quaternion::from_axis_angle(Z, FRAC_PI_2), Quaternion::from_axis_angle(X, FRAC_PI_2));
+		assert!(close((q*p)*Y, Z)); assert!(close((p*q)*Y, -X)); // q*p rotates by p then q
+		let v = xyz{x: 0.3, y: -0.2, z: 0.5}; assert!(close((q*p)*v, q*(p*v))); assert!(close(mat3::from(q*p)*v, (mat3::from(q)*mat3::from(p))*v));
+		assert!(same(q*q.inverse().unwrap(), Quaternion::IDENTITY)); assert!(same(q*q.conjugate(), Quaternion::IDENTITY)); assert!(close((-q)*v, q*v));
+	}
+	#[test] fn slerp_endpoints() {
+		let (a, b) = (Quaternion::from_axis_angle(X, 0.3), Quaternion::from_axis_angle(Z, FRAC_PI_2));
+		assert!(same(slerp(0., a, b), a)); assert!(same(slerp(1., a, b), b)); assert!(same(slerp(1., a, -b), b)); assert!(same(slerp(0.3, b, b), b));
+		let half = Quaternion::from_axis_angle(Z, FRAC_PI_4);
+		assert!(same(slerp(0.5, Quaternion::IDENTITY, b), half)); assert!(same(slerp(0.5, Quaternion::IDENTITY, -b), half)); assert!(same(num::lerp(0.5, Quaternion::IDENTITY, b), half));
+		assert!((slerp(0.25, a, b).norm() - 1.).abs() < 1e-6);
+	}
+}
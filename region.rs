@@ -0,0 +1,121 @@
+use alloc::vec::Vec;
+use crate::{xy, int2, Rect, MinMax};
+
+// Union of disjoint rectangles in y-x banded order: rectangles of a band share their vertical extent and are sorted by x without touching,
+// bands are sorted by y without overlap, and vertically adjacent bands with the same spans are merged.
+#[derive(Clone, Debug, Default, PartialEq, Eq)] pub struct Region { rects: Vec<Rect> }
+
+impl Region {
+	pub fn new() -> Self { Self::default() }
+	pub fn is_empty(&self) -> bool { self.rects.is_empty() }
+	pub fn rects(&self) -> &[Rect] { &self.rects }
+	pub fn iter(&self) -> core::iter::Copied<core::slice::Iter<'_, Rect>> { self.rects.iter().copied() }
+	pub fn area(&self) -> u64 { self.rects.iter().map(|r| { let xy{x,y} = r.size().unsigned(); x as u64 * y as u64 }).sum() } // Rect::area overflows u32
+	pub fn bounds(&self) -> Option<Rect> { crate::reduce_minmax(self.iter()) }
+	// Half open as Rect::is_empty
+	pub fn contains(&self, p: int2) -> bool { band(&self.rects, p.y).iter().any(|r| r.min.x <= p.x && p.x < r.max.x) }
+	pub fn union(&self, b: &Self) -> Self { combine(&self.rects, &b.rects, |a,b| a || b) }
+	pub fn intersection(&self, b: &Self) -> Self { combine(&self.rects, &b.rects, |a,b| a && b) }
+	pub fn subtract(&self, b: &Self) -> Self { combine(&self.rects, &b.rects, |a,b| a && !b) }
+	pub fn union_rect(&self, r: Rect) -> Self { self.union(&r.into()) }
+	pub fn intersection_rect(&self, r: Rect) -> Self { self.intersection(&r.into()) }
+	pub fn subtract_rect(&self, r: Rect) -> Self { self.subtract(&r.into()) }
+}
+
+impl From<Rect> for Region { fn from(r: Rect) -> Self { Self{rects: if r.is_empty() { Vec::new() } else { alloc::vec![r] }} } }
+impl FromIterator<Rect> for Region { fn from_iter<I:IntoIterator<Item=Rect>>(iter: I) -> Self { iter.into_iter().fold(Region::new(), |region, r| region.union_rect(r)) } }
+impl<'t> IntoIterator for &'t Region { type Item = Rect; type IntoIter = core::iter::Copied<core::slice::Iter<'t, Rect>>; fn into_iter(self) -> Self::IntoIter { self.iter() } }
+impl core::ops::BitOr for &Region { type Output = Region; fn bitor(self, b: Self) -> Region { self.union(b) } }
+impl core::ops::BitAnd for &Region { type Output = Region; fn bitand(self, b: Self) -> Region { self.intersection(b) } }
+impl core::ops::Sub for &Region { type Output = Region; fn sub(self, b: Self) -> Region { self.subtract(b) } }
+
+// Rectangles of the band covering y (empty if none)
+fn band(rects: &[Rect], y: i32) -> &[Rect] {
+	let start = rects.partition_point(|r| r.max.y <= y);
+	let Some(first) = rects.get(start) else { return &[] };
+	if first.min.y > y { return &[] }
+	let len = rects[start..].partition_point(|r| r.min.y == first.min.y);
+	&rects[start..start+len]
+}
+
+// Sweeps the vertical then horizontal boundaries of both regions, keeping intervals where op(inside a, inside b)
+fn combine(a: &[Rect], b: &[Rect], op: impl Fn(bool, bool)->bool) -> Region {
+	let mut ys : Vec<i32> = a.iter().chain(b).flat_map(|r| [r.min.y, r.max.y]).collect();
+	ys.sort_unstable(); ys.dedup();
+	let mut rects = Vec::new();
+	let mut last_band = 0; // Start of the last band in rects
+	let mut spans = Vec::new();
+	for y in ys.windows(2) { let [y0, y1] = [y[0], y[1]];
+		let [a, b] = [band(a, y0), band(b, y0)];
+		let mut xs : Vec<i32> = a.iter().chain(b).flat_map(|r| [r.min.x, r.max.x]).collect();
+		xs.sort_unstable(); xs.dedup();
+		spans.clear();
+		let [mut ia, mut ib] = [0, 0];
+		for x in xs.windows(2) { let [x0, x1] = [x[0], x[1]];
+			while ia < a.len() && a[ia].max.x <= x0 { ia += 1; }
+			while ib < b.len() && b[ib].max.x <= x0 { ib += 1; }
+			if !op(ia < a.len() && a[ia].min.x <= x0, ib < b.len() && b[ib].min.x <= x0) { continue; }
+			match spans.last_mut() { Some((_, max)) if *max == x0 => *max = x1, _ => spans.push((x0, x1)) }
+		}
+		if spans.is_empty() { continue; }
+		let previous = &mut rects[last_band..];
+		if previous.first().is_some_and(|r: &Rect| r.max.y == y0) && previous.len() == spans.len() && previous.iter().zip(&spans).all(|(r, &(min, max))| r.min.x == min && r.max.x == max) {
+			for r in previous { r.max.y = y1; }
+		} else {
+			last_band = rects.len();
+			rects.extend(spans.iter().map(|&(x0, x1)| MinMax{min: xy{x: x0, y: y0}, max: xy{x: x1, y: y1}}));
+		}
+	}
+	Region{rects}
+}
+
+#[cfg(test)] mod test {
+	use alloc::vec::Vec;
+	use crate::{xy, int2, Rect};
+	use super::Region;
+	fn r(x0: i32, y0: i32, x1: i32, y1: i32) -> Rect { Rect{min: xy{x: x0, y: y0}, max: xy{x: x1, y: y1}} }
+	// Bands share their vertical extent, are sorted by x without touching, do not overlap and are not mergeable with the band above
+	fn assert_canonical(region: &Region) {
+		let rects = region.rects(); assert!(rects.iter().all(|r| !r.is_empty()));
+		for w in rects.windows(2) { let [a, b] = [w[0], w[1]]; if a.min.y == b.min.y { assert_eq!(a.max.y, b.max.y); assert!(a.max.x < b.min.x, "{rects:?}"); } else { assert!(a.max.y <= b.min.y, "{rects:?}"); } }
+		let bands: Vec<&[Rect]> = rects.chunk_by(|a, b| a.min.y == b.min.y).collect();
+		for w in bands.windows(2) { assert!(!(w[0][0].max.y == w[1][0].min.y && w[0].len() == w[1].len() && w[0].iter().zip(w[1]).all(|(a, b)| a.min.x == b.min.x && a.max.x == b.max.x)), "{rects:?}"); }
+	}
+	fn inside(rects: &[Rect], p: int2) -> bool { rects.iter().any(|r| r.min.x <= p.x && p.x < r.max.x && r.min.y <= p.y && p.y < r.max.y) }
+	#[test] fn overlapping() {
+		let a: Region = [r(0, 0, 10, 10), r(5, 5, 15, 15)].into_iter().collect(); assert_canonical(&a);
+		assert_eq!(a.rects(), [r(0, 0, 10, 5), r(0, 5, 15, 10), r(5, 10, 15, 15)]); assert_eq!(a.area(), 175); assert_eq!(a.bounds(), Some(r(0, 0, 15, 15)));
+		let b = Region::from(r(2, 2, 8, 12));
+		assert_eq!((&a & &b).rects(), [r(2, 2, 8, 10), r(5, 10, 8, 12)]); assert_eq!((&a & &b).area(), 6*8+3*2);
+		assert_eq!((&a - &b).rects(), [r(0, 0, 10, 2), r(0, 2, 2, 5), r(8, 2, 10, 5), r(0, 5, 2, 10), r(8, 5, 15, 10), r(8, 10, 15, 12), r(5, 12, 15, 15)]);
+		assert_eq!((&a | &b).rects(), [r(0, 0, 10, 5), r(0, 5, 15, 10), r(2, 10, 15, 12), r(5, 12, 15, 15)]);
+		for region in [&a & &b, &a - &b, &a | &b] { assert_canonical(&region); }
+		assert_eq!(&a - &a, Region::new()); assert_eq!(&a & &Region::new(), Region::new()); assert_eq!(&a | &Region::new(), a);
+		assert_eq!(Region::from(r(0, 0, 100_000, 70_000)).area(), 7_000_000_000);
+	}
+	#[test] fn touching() {
+		let (a, right, below, corner) = (r(0, 0, 5, 5), r(5, 0, 10, 5), r(0, 5, 5, 10), r(5, 5, 10, 10));
+		assert_eq!(Region::from(a).union_rect(right).rects(), [r(0, 0, 10, 5)]); assert_eq!(Region::from(a).union_rect(below).rects(), [r(0, 0, 5, 10)]);
+		assert_eq!(Region::from(a).union_rect(corner).rects(), [a, corner]); assert_eq!([a, right, below, corner].into_iter().collect::<Region>().rects(), [r(0, 0, 10, 10)]);
+		for b in [right, below, corner] { assert!(Region::from(a).intersection_rect(b).is_empty()); assert_eq!(Region::from(a).subtract_rect(b).rects(), [a]); }
+		let ring = Region::from(r(0, 0, 9, 9)).subtract_rect(r(3, 3, 6, 6)); assert_canonical(&ring);
+		assert_eq!(ring.rects(), [r(0, 0, 9, 3), r(0, 3, 3, 6), r(6, 3, 9, 6), r(0, 6, 9, 9)]); assert_eq!(ring.union_rect(r(3, 3, 6, 6)).rects(), [r(0, 0, 9, 9)]);
+		assert!(Region::from(r(0, 0, 0, 5)).is_empty()); assert!(!ring.contains(xy{x: 3, y: 3})); assert!(ring.contains(xy{x: 2, y: 3})); assert!(!ring.contains(xy{x: 9, y: 0}));
+	}
+	#[test] fn random() {
+		let mut seed = 1u32; let mut random = || { seed = seed.wrapping_mul(1103515245).wrapping_add(12345); ((seed >> 16) % 20) as i32 };
+		for _ in 0..50 {
+			let [a, b]: [Vec<Rect>; 2] = core::array::from_fn(|_| (0..4).map(|_| { let (x, y) = (random(), random()); r(x, y, x+random()/2+1, y+random()/2+1) }).collect());
+			let [ra, rb]: [Region; 2] = [&a, &b].map(|rects| rects.iter().copied().collect());
+			let [union, intersection, difference] = [&ra | &rb, &ra & &rb, &ra - &rb];
+			for region in [&ra, &rb, &union, &intersection, &difference] { assert_canonical(region); }
+			let mut area = [0; 3];
+			for y in -1..32 { for x in -1..32 { let p = xy{x, y}; let [a, b] = [inside(&a, p), inside(&b, p)];
+				assert_eq!([union.contains(p), intersection.contains(p), difference.contains(p)], [a || b, a && b, a && !b]);
+				for (area, inside) in area.iter_mut().zip([a || b, a && b, a && !b]) { *area += inside as u64; }
+			}}
+			assert_eq!([union.area(), intersection.area(), difference.area()], area);
+			assert_eq!(union, difference.union(&rb)); // Same set, same representation
+		}
+	}
+}
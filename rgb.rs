@@ -0,0 +1,41 @@
+use crate::{xy, xyz, xyzw};
+
+vector!(mod_rgb 3 rgb T T T, r g b, R G B);
+vector!(mod_rgba 4 rgba T T T T, r g b a, R G B A);
+// Memory order of BGRA8 surfaces
+vector!(mod_bgra 4 bgra T T T T, b g r a, B G R A);
+// Texture coordinates
+vector!(mod_uv 2 uv T T, u v, U V);
+
+#[allow(non_camel_case_types)] pub type rgb8 = rgb<u8>;
+#[allow(non_camel_case_types)] pub type rgba8 = rgba<u8>;
+#[allow(non_camel_case_types)] pub type bgra8 = bgra<u8>;
+#[allow(non_camel_case_types)] pub type rgbf = rgb<f32>;
+#[allow(non_camel_case_types)] pub type rgbaf = rgba<f32>;
+
+// Renames components (both ways)
+macro_rules! impl_From { ($A:ident $($a:ident)+, $B:ident $($b:ident)+) => {
+	impl<T> From<$A<T>> for $B<T> { fn from(v: $A<T>) -> Self { $B{$($b: v.$a),+} } }
+	impl<T> From<$B<T>> for $A<T> { fn from(v: $B<T>) -> Self { $A{$($a: v.$b),+} } }
+} }
+impl_From!{rgb r g b, xyz x y z}
+impl_From!{rgba r g b a, xyzw x y z w}
+impl_From!{bgra r g b a, rgba r g b a}
+impl_From!{bgra r g b a, xyzw x y z w}
+impl_From!{uv u v, xy x y}
+
+impl<T> rgb<T> { pub fn with_alpha(self, a: T) -> rgba<T> { let rgb{r,g,b} = self; rgba{r,g,b,a} } }
+impl<T> rgba<T> { pub fn rgb(self) -> rgb<T> { let rgba{r,g,b,..} = self; rgb{r,g,b} } }
+
+#[cfg(test)] mod test {
+	use crate::{xy, xyz, xyzw, rgb, rgba, rgba8, bgra8, uv};
+	#[test] fn byte_order() {
+		let c = bgra8{b: 1, g: 2, r: 3, a: 4};
+		assert_eq!(bytemuck::cast::<_, [u8; 4]>(c), [1, 2, 3, 4]); assert_eq!(bytemuck::cast::<_, [u8; 4]>(rgba8::from(c)), [3, 2, 1, 4]);
+		assert_eq!(rgba8::from(c), rgba{r: 3, g: 2, b: 1, a: 4}); assert_eq!(bgra8::from(rgba8::from(c)), c);
+		assert_eq!(xyzw::<u8>::from(c), xyzw{x: 3, y: 2, z: 1, w: 4}); assert_eq!(bgra8::from(xyzw{x: 3, y: 2, z: 1, w: 4}), c); // x is red as for rgba
+		assert_eq!(bytemuck::cast::<_, [u8; 4]>(bgra8::from(rgba8{r: 0xFF, g: 0x80, b: 0, a: 0x40})), [0, 0x80, 0xFF, 0x40]);
+		assert_eq!(rgb::<f32>::from(xyz{x: 1., y: 2., z: 3.}), rgb{r: 1., g: 2., b: 3.}); assert_eq!(xy::<i32>::from(uv{u: 1, v: 2}), xy{x: 1, y: 2});
+		assert_eq!(rgb{r: 1u8, g: 2, b: 3}.with_alpha(4).rgb(), rgb{r: 1, g: 2, b: 3});
+	}
+}
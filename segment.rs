@@ -0,0 +1,138 @@
+use crate::{vec2, int2, MinMax, cross2, dot, sq, norm, orient2d, component_wise_min, component_wise_max};
+
+#[derive(Clone, Copy, Debug, PartialEq)] pub enum SegmentIntersection { Point(vec2), Segment(vec2, vec2) }
+
+fn straddles(a: f64, b: f64) -> bool { (a <= 0. && b >= 0.) || (a >= 0. && b <= 0.) }
+// Closed segments a b and c d. Classified with exact orientation: touching endpoints are returned exactly, collinear overlaps as the shared segment (ordered along a b)
+pub fn segment_intersection(a: vec2, b: vec2, c: vec2, d: vec2) -> Option<SegmentIntersection> {
+	let (abc, abd, cda, cdb) = (orient2d(a, b, c), orient2d(a, b, d), orient2d(c, d, a), orient2d(c, d, b));
+	if abc == 0. && abd == 0. && cda == 0. && cdb == 0. {
+		// Overlap along the axis of largest extent
+		let extent = component_wise_max(component_wise_max(a, b), component_wise_max(c, d)) - component_wise_min(component_wise_min(a, b), component_wise_min(c, d));
+		let key = |p: vec2| if extent.x >= extent.y { p.x } else { p.y };
+		let ascending = key(a) <= key(b);
+		let order = |p: vec2, q: vec2| if (key(p) <= key(q)) == ascending { (p, q) } else { (q, p) };
+		let ((a, b), (c, d)) = (order(a, b), order(c, d));
+		let before = |p: vec2, q: vec2| if ascending { key(p) < key(q) } else { key(p) > key(q) };
+		let (start, end) = (if before(a, c) { c } else { a }, if before(d, b) { d } else { b });
+		return if before(end, start) { None } else if start == end { Some(SegmentIntersection::Point(start)) } else { Some(SegmentIntersection::Segment(start, end)) }
+	}
+	if !straddles(abc, abd) || !straddles(cda, cdb) { return None }
+	Some(SegmentIntersection::Point(
+		if abc == 0. { c } else if abd == 0. { d } else if cda == 0. { a } else if cdb == 0. { b }
+		else {
+			// Rounding may only move the point within both bounding boxes
+			let t = (cross2(c - a, d - c) / cross2(b - a, d - c)).clamp(0., 1.);
+			let (min, max) = (component_wise_max(component_wise_min(a, b), component_wise_min(c, d)), component_wise_min(component_wise_max(a, b), component_wise_max(c, d)));
+			component_wise_max(min, component_wise_min(max, a + t*(b - a)))
+		}
+	))
+}
+
+// Infinite lines through a b and c d, None when parallel
+pub fn line_intersection(a: vec2, b: vec2, c: vec2, d: vec2) -> Option<vec2> {
+	let det = cross2(b - a, d - c);
+	(det != 0.).then(|| a + (cross2(c - a, d - c) / det)*(b - a))
+}
+
+pub fn closest_point_on_segment(p: vec2, a: vec2, b: vec2) -> vec2 {
+	let sq = sq(b - a);
+	if sq == 0. { return a }
+	let t = dot(p - a, b - a) / sq;
+	if t <= 0. { a } else if t >= 1. { b } else { a + t*(b - a) }
+}
+pub fn segment_distance(p: vec2, a: vec2, b: vec2) -> f32 { norm(p - closest_point_on_segment(p, a, b)) }
+
+impl MinMax<vec2> {
+	// Liang-Barsky: part of the segment a b within the closed box. Unclipped endpoints are kept exactly, clipped ones are clamped to the box
+	pub fn clip_segment(self, a: vec2, b: vec2) -> Option<(vec2, vec2)> {
+		let d = b - a;
+		let (mut t0, mut t1) = (0f32, 1f32);
+		for (p, q) in [(-d.x, a.x - self.min.x), (d.x, self.max.x - a.x), (-d.y, a.y - self.min.y), (d.y, self.max.y - a.y)] {
+			if p == 0. { if q < 0. { return None } continue } // Parallel outside
+			let t = q / p;
+			if p < 0. { t0 = t0.max(t); } else { t1 = t1.min(t); }
+			if t0 > t1 { return None }
+		}
+		let clamp = |p: vec2| component_wise_max(self.min, component_wise_min(self.max, p));
+		Some((if t0 > 0. { clamp(a + t0*d) } else { a }, if t1 < 1. { clamp(a + t1*d) } else { b }))
+	}
+}
+// Against the pixel bounds (closed box from min to max)
+impl MinMax<int2> { pub fn clip_segment(self, a: vec2, b: vec2) -> Option<(vec2, vec2)> { self.map(vec2::from).clip_segment(a, b) } }
+
+#[cfg(test)] mod test {
+	use crate::{xy, vec2, MinMax, Rect, segment_intersection, line_intersection, closest_point_on_segment, segment_distance, SegmentIntersection::*};
+	fn p(x: f32, y: f32) -> vec2 { xy{x, y} }
+	#[test] fn crossing() {
+		assert_eq!(segment_intersection(p(0., 0.), p(2., 2.), p(0., 2.), p(2., 0.)), Some(Point(p(1., 1.))));
+		assert_eq!(segment_intersection(p(0., 0.), p(1., 1.), p(0., 2.), p(2., 4.)), None);
+		assert_eq!(line_intersection(p(0., 0.), p(1., 0.), p(5., 2.), p(5., 3.)), Some(p(5., 0.)));
+		// Nearly parallel: rounding stays within both boxes
+		let Some(Point(i)) = segment_intersection(p(0., 0.), p(1e4, 1.), p(0., 1e-6), p(1e4, 1. - 1e-3)) else { panic!() };
+		assert!(i.x >= 0. && i.x <= 1e4 && i.y >= 0. && i.y <= 1.);
+	}
+	#[test] fn parallel() {
+		assert_eq!(segment_intersection(p(0., 0.), p(1., 0.), p(0., 1.), p(1., 1.)), None);
+		assert_eq!(segment_intersection(p(0., 0.), p(4., 2.), p(0., 1.), p(4., 3.)), None);
+		assert_eq!(line_intersection(p(0., 0.), p(1., 1.), p(0., 1.), p(1., 2.)), None);
+		assert_eq!(line_intersection(p(0., 0.), p(1., 1.), p(2., 2.), p(3., 3.)), None); // Same line
+	}
+	#[test] fn collinear() {
+		assert_eq!(segment_intersection(p(0., 0.), p(4., 0.), p(2., 0.), p(6., 0.)), Some(Segment(p(2., 0.), p(4., 0.))));
+		// Ordered along a b
+		assert_eq!(segment_intersection(p(4., 0.), p(0., 0.), p(2., 0.), p(6., 0.)), Some(Segment(p(4., 0.), p(2., 0.))));
+		assert_eq!(segment_intersection(p(0., 0.), p(0., 4.), p(0., 5.), p(0., 1.)), Some(Segment(p(0., 1.), p(0., 4.))));
+		assert_eq!(segment_intersection(p(0., 0.), p(6., 3.), p(2., 1.), p(4., 2.)), Some(Segment(p(2., 1.), p(4., 2.)))); // Contained
+		assert_eq!(segment_intersection(p(0., 0.), p(2., 2.), p(2., 2.), p(3., 3.)), Some(Point(p(2., 2.)))); // End to end
+		assert_eq!(segment_intersection(p(0., 0.), p(1., 1.), p(2., 2.), p(3., 3.)), None); // Gap
+	}
+	#[test] fn touching() {
+		// Endpoints on the other segment are returned exactly
+		assert_eq!(segment_intersection(p(0., 0.), p(2., 0.), p(0.3, 0.), p(0.7, 5.)), Some(Point(p(0.3, 0.))));
+		assert_eq!(segment_intersection(p(0.3, 0.), p(0.7, 5.), p(0., 0.), p(2., 0.)), Some(Point(p(0.3, 0.))));
+		assert_eq!(segment_intersection(p(0., 0.), p(2., 0.), p(2., 0.), p(3., 1.)), Some(Point(p(2., 0.))));
+		assert_eq!(segment_intersection(p(0.1, 0.7), p(0.3, 0.2), p(0.3, 0.2), p(0.9, 0.4)), Some(Point(p(0.3, 0.2))));
+		assert_eq!(segment_intersection(p(0., 0.), p(2., 0.), p(2., 1e-30), p(3., 1.)), None); // Just above
+	}
+	#[test] fn zero_length() {
+		assert_eq!(segment_intersection(p(1., 1.), p(1., 1.), p(0., 0.), p(2., 2.)), Some(Point(p(1., 1.))));
+		assert_eq!(segment_intersection(p(0., 0.), p(2., 2.), p(1., 1.), p(1., 1.)), Some(Point(p(1., 1.))));
+		assert_eq!(segment_intersection(p(1., 1.), p(1., 1.), p(1., 1.), p(1., 1.)), Some(Point(p(1., 1.))));
+		assert_eq!(segment_intersection(p(1., 1.), p(1., 1.), p(2., 2.), p(2., 2.)), None);
+		assert_eq!(segment_intersection(p(1., 2.), p(1., 2.), p(0., 0.), p(2., 2.)), None); // Off the line
+		assert_eq!(segment_intersection(p(5., 5.), p(5., 5.), p(0., 0.), p(2., 2.)), None); // On the line, beyond the segment
+		assert_eq!(line_intersection(p(1., 1.), p(1., 1.), p(0., 0.), p(2., 0.)), None);
+		assert_eq!(closest_point_on_segment(p(3., -1.), p(1., 1.), p(1., 1.)), p(1., 1.));
+	}
+	#[test] fn distance() {
+		assert_eq!(closest_point_on_segment(p(1., 5.), p(0., 0.), p(2., 0.)), p(1., 0.));
+		assert_eq!(closest_point_on_segment(p(-1., 5.), p(0., 0.), p(2., 0.)), p(0., 0.));
+		assert_eq!(closest_point_on_segment(p(3., -1.), p(0., 0.), p(2., 0.)), p(2., 0.));
+		assert_eq!(segment_distance(p(5., 4.), p(0., 0.), p(2., 0.)), 5.);
+		assert_eq!(segment_distance(p(1., -3.), p(0., 0.), p(2., 0.)), 3.);
+	}
+	#[test] fn clip() {
+		let r = MinMax{min: p(0., 0.), max: p(4., 2.)};
+		// Inside: unchanged
+		assert_eq!(r.clip_segment(p(1., 1.), p(3., 1.)), Some((p(1., 1.), p(3., 1.))));
+		assert_eq!(r.clip_segment(p(0.1, 0.3), p(3.7, 1.9)), Some((p(0.1, 0.3), p(3.7, 1.9))));
+		assert_eq!(r.clip_segment(p(2., 1.), p(2., 1.)), Some((p(2., 1.), p(2., 1.))));
+		// Outside
+		assert_eq!(r.clip_segment(p(5., 0.), p(5., 2.)), None);
+		assert_eq!(r.clip_segment(p(-1., 3.), p(5., 3.)), None);
+		assert_eq!(r.clip_segment(p(-1., 1.), p(1., 4.)), None); // Passes outside the corner
+		assert_eq!(r.clip_segment(p(5., 5.), p(5., 5.)), None);
+		// Crossing
+		assert_eq!(r.clip_segment(p(-2., 1.), p(6., 1.)), Some((p(0., 1.), p(4., 1.))));
+		assert_eq!(r.clip_segment(p(6., 1.), p(-2., 1.)), Some((p(4., 1.), p(0., 1.))));
+		assert_eq!(r.clip_segment(p(-1., -1.), p(3., 3.)), Some((p(0., 0.), p(2., 2.))));
+		assert_eq!(r.clip_segment(p(2., 1.), p(2., 5.)), Some((p(2., 1.), p(2., 2.))));
+		assert_eq!(r.clip_segment(p(4., 0.), p(4., 5.)), Some((p(4., 0.), p(4., 2.)))); // Along the edge
+		assert_eq!(r.clip_segment(p(3., 3.), p(5., 1.)), Some((p(4., 2.), p(4., 2.)))); // Corner
+		let rect: Rect = MinMax{min: xy{x: 0, y: 0}, max: xy{x: 4, y: 2}};
+		assert_eq!(rect.clip_segment(p(-2., 1.), p(6., 1.)), Some((p(0., 1.), p(4., 1.))));
+		// Clipped points stay inside
+		for k in 0..100 { let a = k as f32 * 0.0731; if let Some((s, e)) = r.clip_segment(p(-3.3 + a, -1.7), p(7.1, 3.9 - a)) { assert!(r.contains(s) && r.contains(e)); } }
+	}
+}
@@ -0,0 +1,33 @@
+use core::ops::{Add, AddAssign};
+use crate::Float;
+
+// Neumaier (improved Kahan) summation: error within a few ulp of the sum of magnitudes regardless of the number of terms
+#[derive(Clone, Copy, Debug, PartialEq)] pub struct CompensatedSum<T> { sum: T, compensation: T }
+impl<T:Float> CompensatedSum<T> {
+	pub fn new() -> Self { Self{sum: T::from(0.), compensation: T::from(0.)} }
+	pub fn value(self) -> T { self.sum + self.compensation }
+}
+impl<T:Float> Default for CompensatedSum<T> { fn default() -> Self { Self::new() } }
+impl<T:Float> AddAssign<T> for CompensatedSum<T> { fn add_assign(&mut self, x: T) {
+	let sum = self.sum + x;
+	// Low order bits lost by the larger magnitude
+	self.compensation = self.compensation + if self.sum.abs() >= x.abs() { (self.sum - sum) + x } else { (x - sum) + self.sum };
+	self.sum = sum;
+} }
+pub fn compensated_sum<T:Float>(iter: impl IntoIterator<Item=T>) -> T { let mut sum = CompensatedSum::new(); for x in iter { sum += x; } sum.value() }
+
+// Recursive halves: error grows with log n instead of n, at the cost of a sequential sum (also for vectors)
+pub fn pairwise_sum<T:Copy+Add<Output=T>+num::Zero>(values: &[T]) -> T {
+	if values.len() <= 8 { values.iter().fold(T::ZERO, |sum, &x| sum + x) }
+	else { let (a, b) = values.split_at(values.len()/2); pairwise_sum(a) + pairwise_sum(b) }
+}
+
+#[cfg(test)] mod test {
+	use crate::{xy, compensated_sum, pairwise_sum, CompensatedSum};
+	#[test] fn cancellation() {
+		assert_eq!([1e16, 1., -1e16].into_iter().sum::<f64>(), 0.); assert_eq!(compensated_sum([1e16, 1., -1e16]), 1.); // The 1 is lost to rounding by a naive sum
+		assert_eq!(compensated_sum([1., 1e16, -1e16]), 1.); assert_eq!(compensated_sum([1e16f32, 1., -1e16]), 1.); assert_eq!(compensated_sum([1e100, 1., -1e100, 1.]), 2.);
+		let mut sum = CompensatedSum::default(); for _ in 0..10 { sum += 0.1f64; } assert_eq!(sum.value(), 1.); assert_eq!(compensated_sum::<f64>([]), 0.);
+		assert!((pairwise_sum(&[0.1f32; 1 << 20]) - 104857.6).abs() < 0.02); assert!(([0.1f32; 1 << 20].iter().sum::<f32>() - 104857.6).abs() > 100.); assert_eq!(pairwise_sum(&[xy{x: 1, y: 2}; 20]), xy{x: 20, y: 40}); assert_eq!(pairwise_sum::<f64>(&[]), 0.);
+	}
+}
@@ -0,0 +1,30 @@
+use crate::{xy, xyz, xyzw};
+
+// Generates read swizzles: name components, ... per output vector. By move when each component is used at most once, otherwise by copy
+macro_rules! impl_swizzle { ($Vector:ident $($Output:ident [$($name:ident $($c:ident)+),*] [$($copy:ident $($r:ident)+),*])+) => {
+	impl<T> $Vector<T> { $($( pub fn $name(self) -> $Output<T> { let $Vector{$($c),+, ..} = self; $Output::from([$($c),+]) } )*)+ }
+	impl<T:Copy> $Vector<T> { $($( pub fn $copy(self) -> $Output<T> { $Output::from([$(self.$r),+]) } )*)+ }
+} }
+include!(concat!(env!("OUT_DIR"), "/swizzle.rs")); // Invocations generated by build.rs
+
+// Component replacement
+impl<T> xy<T> { pub fn with_x(self, x: T) -> Self { xy{x, ..self} } pub fn with_y(self, y: T) -> Self { xy{y, ..self} } }
+impl<T> xyz<T> { pub fn with_x(self, x: T) -> Self { xyz{x, ..self} } pub fn with_y(self, y: T) -> Self { xyz{y, ..self} } pub fn with_z(self, z: T) -> Self { xyz{z, ..self} } }
+impl<T> xyzw<T> { pub fn with_x(self, x: T) -> Self { xyzw{x, ..self} } pub fn with_y(self, y: T) -> Self { xyzw{y, ..self} } pub fn with_z(self, z: T) -> Self { xyzw{z, ..self} } pub fn with_w(self, w: T) -> Self { xyzw{w, ..self} } }
+
+// Extension by a constant component
+impl<T:num::Zero> xy<T> { pub fn xy0(self) -> xyz<T> { let xy{x,y} = self; xyz{x, y, z: T::ZERO} } }
+impl<T:From<u8>> xy<T> { pub fn xy1(self) -> xyz<T> { let xy{x,y} = self; xyz{x, y, z: T::from(1)} } }
+impl<T:num::Zero> xyz<T> { pub fn xyz0(self) -> xyzw<T> { let xyz{x,y,z} = self; xyzw{x, y, z, w: T::ZERO} } }
+impl<T:From<u8>> xyz<T> { pub fn xyz1(self) -> xyzw<T> { let xyz{x,y,z} = self; xyzw{x, y, z, w: T::from(1)} } }
+
+#[cfg(test)] mod test {
+	use crate::{xy, xyz, xyzw};
+	#[derive(Debug, PartialEq)] struct Moved(u8); // Not Copy
+	#[test] fn by_move() {
+		assert_eq!(xy{x: Moved(1), y: Moved(2)}.yx(), xy{x: Moved(2), y: Moved(1)});
+		assert_eq!(xyz{x: Moved(1), y: Moved(2), z: Moved(3)}.zx(), xy{x: Moved(3), y: Moved(1)});
+		assert_eq!(xyzw{x: Moved(1), y: Moved(2), z: Moved(3), w: Moved(4)}.wzyx(), xyzw{x: Moved(4), y: Moved(3), z: Moved(2), w: Moved(1)});
+		assert_eq!(xyz{x: 1, y: 2, z: 3}.zzxy(), xyzw{x: 3, y: 3, z: 1, w: 2}); assert_eq!(xy{x: 1, y: 2}.xxy(), xyz{x: 1, y: 1, z: 2});
+	}
+}
@@ -0,0 +1,91 @@
+use crate::{xyz, vec3, mat3, mat4x4, Matrix, dot, cross, normalize};
+#[allow(unused_imports)] use crate::Float;
+
+// Column vectors (M*v), right handed: x right, y up, z toward the viewer. Clip depth maps near..far to 0..1
+impl mat3 {
+	pub fn scale(xyz{x,y,z}: vec3) -> Self { Self::diagonal([x,y,z]) }
+}
+impl mat4x4 {
+	pub fn translation(xyz{x,y,z}: vec3) -> Self { Matrix([[1.,0.,0.,x],[0.,1.,0.,y],[0.,0.,1.,z],[0.,0.,0.,1.]]) }
+	pub fn scale(xyz{x,y,z}: vec3) -> Self { Self::diagonal([x,y,z,1.]) }
+	pub fn orthographic_rh(left: f32, right: f32, bottom: f32, top: f32, near: f32, far: f32) -> Self { let [w,h,d] = [right-left, top-bottom, near-far];
+		Matrix([[2./w,0.,0.,-(right+left)/w],[0.,2./h,0.,-(top+bottom)/h],[0.,0.,1./d,near/d],[0.,0.,0.,1.]])
+	}
+	pub fn orthographic_lh(left: f32, right: f32, bottom: f32, top: f32, near: f32, far: f32) -> Self { let [w,h,d] = [right-left, top-bottom, far-near];
+		Matrix([[2./w,0.,0.,-(right+left)/w],[0.,2./h,0.,-(top+bottom)/h],[0.,0.,1./d,-near/d],[0.,0.,0.,1.]])
+	}
+	// Points have w=1 and are translated, directions have w=0 and are not
+	pub fn transform_point(self, p: vec3) -> vec3 { (self * p.xyz1()).homogeneous_divide() }
+	pub fn transform_vector(self, v: vec3) -> vec3 { (self * v.xyz0()).xyz() }
+}
+impl mat3 {
+	pub fn rotation_x(angle: f32) -> Self { let (s,c) = f32::sin_cos(angle); Matrix([[1.,0.,0.],[0.,c,-s],[0.,s,c]]) }
+	pub fn rotation_y(angle: f32) -> Self { let (s,c) = f32::sin_cos(angle); Matrix([[c,0.,s],[0.,1.,0.],[-s,0.,c]]) }
+	pub fn rotation_z(angle: f32) -> Self { let (s,c) = f32::sin_cos(angle); Matrix([[c,-s,0.],[s,c,0.],[0.,0.,1.]]) }
+	pub fn rotation(axis: vec3, angle: f32) -> Self { crate::Quaternion::from_axis_angle(axis, angle).into() }
+}
+impl mat4x4 {
+	pub fn rotation_x(angle: f32) -> Self { mat3::rotation_x(angle).into() }
+	pub fn rotation_y(angle: f32) -> Self { mat3::rotation_y(angle).into() }
+	pub fn rotation_z(angle: f32) -> Self { mat3::rotation_z(angle).into() }
+	pub fn rotation(axis: vec3, angle: f32) -> Self { mat3::rotation(axis, angle).into() }
+	// View transform looking from eye toward center along -z
+	pub fn look_at_rh(eye: vec3, center: vec3, up: vec3) -> Self {
+		let f = normalize(center-eye); let s = normalize(cross(f, up)); let u = cross(s, f);
+		Matrix([[s.x,s.y,s.z,-dot(s,eye)],[u.x,u.y,u.z,-dot(u,eye)],[-f.x,-f.y,-f.z,dot(f,eye)],[0.,0.,0.,1.]])
+	}
+	// View transform looking from eye toward center along +z
+	pub fn look_at_lh(eye: vec3, center: vec3, up: vec3) -> Self {
+		let f = normalize(center-eye); let s = normalize(cross(up, f)); let u = cross(f, s);
+		Matrix([[s.x,s.y,s.z,-dot(s,eye)],[u.x,u.y,u.z,-dot(u,eye)],[f.x,f.y,f.z,-dot(f,eye)],[0.,0.,0.,1.]])
+	}
+	pub fn perspective_rh(fov_y: f32, aspect: f32, near: f32, far: f32) -> Self { let f = 1./f32::tan(fov_y/2.); let d = near-far;
+		Matrix([[f/aspect,0.,0.,0.],[0.,f,0.,0.],[0.,0.,far/d,near*far/d],[0.,0.,-1.,0.]])
+	}
+	pub fn perspective_lh(fov_y: f32, aspect: f32, near: f32, far: f32) -> Self { let f = 1./f32::tan(fov_y/2.); let d = far-near;
+		Matrix([[f/aspect,0.,0.,0.],[0.,f,0.,0.],[0.,0.,far/d,-near*far/d],[0.,0.,1.,0.]])
+	}
+	pub fn perspective_infinite_rh(fov_y: f32, aspect: f32, near: f32) -> Self { let f = 1./f32::tan(fov_y/2.);
+		Matrix([[f/aspect,0.,0.,0.],[0.,f,0.,0.],[0.,0.,-1.,-near],[0.,0.,-1.,0.]])
+	}
+	pub fn perspective_infinite_lh(fov_y: f32, aspect: f32, near: f32) -> Self { let f = 1./f32::tan(fov_y/2.);
+		Matrix([[f/aspect,0.,0.,0.],[0.,f,0.,0.],[0.,0.,1.,-near],[0.,0.,1.,0.]])
+	}
+}
+
+#[cfg(test)] mod test {
+	use crate::{xyz, xyzw, vec3, mat3, mat4x4};
+	fn close(a: vec3, b: vec3) -> bool { (a-b).into_iter().all(|e| e.abs() < 1e-4) }
+	#[test] fn depth() {
+		for (p, z) in [(mat4x4::perspective_rh(1., 1.5, 0.1, 100.), -1.), (mat4x4::perspective_lh(1., 1.5, 0.1, 100.), 1.)] { // rh looks down -z, lh down +z
+			assert!(p.transform_point(xyz{x: 0., y: 0., z: z*0.1}).z.abs() < 1e-5); assert!((p.transform_point(xyz{x: 0., y: 0., z: z*100.}).z - 1.).abs() < 1e-5);
+			let mid = p.transform_point(xyz{x: 0., y: 0., z: z*1.}).z; assert!(0. < mid && mid < 1.);
+			assert!((p * xyzw{x: 0., y: 0., z, w: 1.}).w > 0.); assert!((p * xyzw{x: 0., y: 0., z: -z, w: 1.}).w < 0.); // Behind the eye
+		}
+		for (p, z) in [(mat4x4::perspective_infinite_rh(1., 1.5, 0.1), -1.), (mat4x4::perspective_infinite_lh(1., 1.5, 0.1), 1.)] {
+			assert!(p.transform_point(xyz{x: 0., y: 0., z: z*0.1}).z.abs() < 1e-5); assert!((p.transform_point(xyz{x: 0., y: 0., z: z*1e7}).z - 1.).abs() < 1e-5);
+		}
+		let o = mat4x4::orthographic_rh(-1., 3., 0., 2., 1., 11.);
+		assert!(close(o.transform_point(xyz{x: -1., y: 0., z: -1.}), xyz{x: -1., y: -1., z: 0.})); assert!(close(o.transform_point(xyz{x: 3., y: 2., z: -11.}), xyz{x: 1., y: 1., z: 1.}));
+		let o = mat4x4::orthographic_lh(-1., 3., 0., 2., 1., 11.);
+		assert!(close(o.transform_point(xyz{x: -1., y: 0., z: 1.}), xyz{x: -1., y: -1., z: 0.})); assert!(close(o.transform_point(xyz{x: 3., y: 2., z: 11.}), xyz{x: 1., y: 1., z: 1.}));
+	}
+	#[test] fn look_at() {
+		let (eye, center, up) = (xyz{x: 1., y: 2., z: 3.}, xyz{x: -2., y: 0., z: 1.}, xyz{x: 0., y: 1., z: 0.});
+		let distance = crate::norm(center-eye);
+		let v = mat4x4::look_at_rh(eye, center, up); assert!(close(v.transform_point(eye), xyz{x: 0., y: 0., z: 0.})); assert!(close(v.transform_point(center), xyz{x: 0., y: 0., z: -distance}));
+		assert!(v.transform_point(eye + up).y > 0.); assert!((mat3::from(v).det() - 1.).abs() < 1e-5);
+		let v = mat4x4::look_at_lh(eye, center, up); assert!(close(v.transform_point(eye), xyz{x: 0., y: 0., z: 0.})); assert!(close(v.transform_point(center), xyz{x: 0., y: 0., z: distance}));
+		assert!(v.transform_point(eye + up).y > 0.);
+		// Same world points: the lh view basis is the rh one rotated half a turn around up
+		let (rh, lh) = (mat4x4::look_at_rh(eye, center, up).transform_point(xyz{x: 0., y: 5., z: 0.}), mat4x4::look_at_lh(eye, center, up).transform_point(xyz{x: 0., y: 5., z: 0.}));
+		assert!(close(rh, xyz{x: -lh.x, y: lh.y, z: -lh.z}));
+	}
+	#[test] fn affine() {
+		let t = mat4x4::translation(xyz{x: 1., y: 2., z: 3.});
+		assert_eq!(t.transform_point(xyz{x: 1., y: 1., z: 1.}), xyz{x: 2., y: 3., z: 4.}); assert_eq!(t.transform_vector(xyz{x: 1., y: 1., z: 1.}), xyz{x: 1., y: 1., z: 1.});
+		assert_eq!(mat4x4::scale(xyz{x: 2., y: 3., z: 4.}).transform_point(xyz{x: 1., y: 1., z: 1.}), xyz{x: 2., y: 3., z: 4.});
+		assert!(close(mat4x4::rotation_z(core::f32::consts::FRAC_PI_2).transform_vector(xyz{x: 1., y: 0., z: 0.}), xyz{x: 0., y: 1., z: 0.}));
+		for (m, axis) in [(mat3::rotation_x(0.4), xyz{x: 1., y: 0., z: 0.}), (mat3::rotation_y(0.4), xyz{x: 0., y: 1., z: 0.})] { assert!(close(m*xyz{x: 0.5, y: 1., z: 2.}, mat3::rotation(axis, 0.4)*xyz{x: 0.5, y: 1., z: 2.})); }
+	}
+}
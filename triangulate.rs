@@ -0,0 +1,129 @@
+#[cfg(feature="alloc")] use alloc::{vec, vec::Vec, collections::{BTreeMap, BTreeSet}};
+use crate::{xy, vec2, cross2, orient2d, Polygon};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)] pub enum Predicates { #[default] Fast, Exact }
+impl Predicates { fn orient(self, a: vec2, b: vec2, c: vec2) -> f64 { match self { Self::Fast => cross2(a - c, b - c) as f64, Self::Exact => orient2d(a, b, c) } } }
+
+// Ear clipping of a simple polygon with holes (each hole is bridged to the outline through a visible vertex).
+// Indices refer to the vertices of outer then of each hole in order. Triangles are counterclockwise whatever the input winding.
+// ring is scratch for n+2h (vertex index, position), triangles receives up to n+2h-2, for n vertices and h holes.
+// Collinear and duplicate vertices are clipped without a triangle. Without any valid ear (i.e not simple), a vertex is clipped anyway so that it always terminates (with a triangle only when convex).
+#[track_caller] pub fn triangulate_into(outer: &[vec2], holes: &[&[vec2]], predicates: Predicates, ring: &mut [(u32, vec2)], triangles: &mut [[u32; 3]]) -> usize {
+	let orient = |a, b, c| predicates.orient(a, b, c);
+	if outer.len() < 3 { return 0 }
+	let holes_len = holes.iter().filter(|h| h.len() >= 3).map(|h| h.len() + 2).sum::<usize>();
+	assert!(ring.len() >= outer.len() + holes_len && triangles.len() >= outer.len() + holes_len - 2);
+	// Outline counterclockwise, holes clockwise
+	let ccw = Polygon(outer).signed_area() >= 0.;
+	for (i, &p) in outer.iter().enumerate() { ring[if ccw { i } else { outer.len()-1-i }] = (i as u32, p); }
+	let mut len = outer.len();
+	// Bridges holes from the rightmost, so that bridges never cross holes yet to be bridged
+	let max_x = |h: &[vec2]| h.iter().map(|p| p.x).fold(f32::NEG_INFINITY, f32::max);
+	let mut order: Option<(f32, usize)> = None;
+	while let Some(k) = (0..holes.len()).filter(|&k| holes[k].len() >= 3 && order.is_none_or(|o| (max_x(holes[k]), k) < o)).max_by(|&a, &b| (max_x(holes[a]), a).partial_cmp(&(max_x(holes[b]), b)).unwrap()) {
+		order = Some((max_x(holes[k]), k));
+		let (hole, offset) = (holes[k], outer.len() + holes[..k].iter().map(|h| h.len()).sum::<usize>());
+		let ccw = Polygon(hole).signed_area() > 0.;
+		let m = (0..hole.len()).max_by(|&a, &b| hole[a].x.partial_cmp(&hole[b].x).unwrap()).unwrap();
+		let hm = hole[m];
+		// Nearest edge crossing the ray to the right of M (going up as the interior is on the left)
+		let mut hit: Option<(f32, usize)> = None;
+		for i in 0..len {
+			let (a, b) = (ring[i].1, ring[(i+1)%len].1);
+			if !(a.y <= hm.y && hm.y <= b.y && a.y < b.y) { continue }
+			let x = a.x + (hm.y - a.y) * (b.x - a.x) / (b.y - a.y);
+			if x >= hm.x && hit.is_none_or(|(hx, _)| x < hx) { hit = Some((x, if a.x > b.x { i } else { (i+1)%len })); }
+		}
+		let Some((x, mut bridge)) = hit else { continue }; // Outside the outline
+		// The vertex within the triangle M I P with the smallest angle to the ray is visible from M (Eberly)
+		let (i, p) = (xy{x, y: hm.y}, ring[bridge].1);
+		let side = |a, b, c| { let o = orient(a, b, c); if o > 0. { 1 } else if o < 0. { -1 } else { 0 } };
+		let inside = |r| { let s = [side(hm, i, r), side(i, p, r), side(p, hm, r)]; !(s.contains(&1) && s.contains(&-1)) };
+		let tan = |r: vec2| ((r.y - hm.y).abs() / (r.x - hm.x), r.x - hm.x);
+		for j in 0..len { let r = ring[j].1; if r.x >= hm.x && r.x <= p.x && r != p && r != hm && inside(r) && tan(r) < tan(ring[bridge].1) { bridge = j; } }
+		// Among copies of a vertex already bridged, the one whose interior sector contains M
+		let locally_inside = |j: usize| {
+			let (a, v, b) = (ring[(j+len-1)%len].1, ring[j].1, ring[(j+1)%len].1);
+			if orient(a, v, b) >= 0. { orient(a, v, hm) >= 0. && orient(v, b, hm) >= 0. } else { orient(a, v, hm) > 0. || orient(v, b, hm) > 0. }
+		};
+		if let Some(j) = (0..len).find(|&j| ring[j].1 == ring[bridge].1 && locally_inside(j)) { bridge = j; }
+		// P, M, hole cycle, M, P
+		let n = hole.len() + 2;
+		ring.copy_within(bridge+1..len, bridge+1+n);
+		for j in 0..=hole.len() { let h = if ccw { (m + hole.len() - j) % hole.len() } else { (m + j) % hole.len() }; ring[bridge+1+j] = ((offset + h) as u32, hole[h]); }
+		ring[bridge+1+hole.len()+1] = ring[bridge];
+		len += n;
+	}
+	let ring = &mut ring[..len];
+	let mut count = 0;
+	let (mut i, mut stall) = (0, 0);
+	while len > 2 {
+		i %= len;
+		let [(ip, p), (iv, v), (in_, n)] = [ring[(i+len-1)%len], ring[i], ring[(i+1)%len]];
+		let o = orient(p, v, n);
+		let ear = o > 0. && !(0..len).any(|j| { let r = ring[j].1; r != p && r != v && r != n && orient(p, v, r) >= 0. && orient(v, n, r) >= 0. && orient(n, p, r) >= 0. });
+		if o != 0. && !ear && stall < len { i += 1; stall += 1; continue }
+		if o > 0. { triangles[count] = [ip, iv, in_]; count += 1; } // A forced reflex vertex is dropped, which keeps every triangle counterclockwise
+		ring.copy_within(i+1..len, i);
+		len -= 1;
+		stall = 0;
+		i = i.saturating_sub(1); // Previous vertex may have become an ear
+	}
+	count
+}
+
+#[cfg(feature="alloc")] pub fn triangulate(outer: &[vec2], holes: &[&[vec2]], predicates: Predicates) -> Vec<[u32; 3]> {
+	let n = outer.len() + holes.iter().map(|h| h.len() + 2).sum::<usize>();
+	let mut triangles = vec![[0; 3]; n.saturating_sub(2)];
+	let len = triangulate_into(outer, holes, predicates, &mut vec![(0, xy{x: 0., y: 0.}); n], &mut triangles);
+	triangles.truncate(len);
+	triangles
+}
+
+// Constrained Delaunay: flips ear clipping edges (except polygon edges) until no vertex is within the circumcircle of an adjacent triangle.
+// Flips always use the exact incircle, which guarantees termination.
+#[cfg(feature="alloc")] pub fn triangulate_delaunay(outer: &[vec2], holes: &[&[vec2]], predicates: Predicates) -> Vec<[u32; 3]> {
+	let mut triangles = triangulate(outer, holes, predicates);
+	let points: Vec<vec2> = outer.iter().chain(holes.iter().copied().flatten()).copied().collect();
+	let mut constraints = BTreeSet::new();
+	let mut offset = 0;
+	for polygon in core::iter::once(outer).chain(holes.iter().copied()) {
+		let n = polygon.len() as u32;
+		for i in 0..n { let (a, b) = (offset + i, offset + (i+1)%n); constraints.insert((a.min(b), a.max(b))); }
+		offset += n;
+	}
+	let mut edges: BTreeMap<(u32, u32), usize> = triangles.iter().enumerate().flat_map(|(t, &[a, b, c])| [((a, b), t), ((b, c), t), ((c, a), t)]).collect();
+	let mut stack: Vec<(u32, u32)> = edges.keys().copied().collect();
+	let third = |[a, b, c]: [u32; 3], e: (u32, u32)| if e == (a, b) { c } else if e == (b, c) { a } else { b };
+	while let Some((a, b)) = stack.pop() {
+		if constraints.contains(&(a.min(b), a.max(b))) { continue }
+		let (Some(&t), Some(&u)) = (edges.get(&(a, b)), edges.get(&(b, a))) else { continue };
+		let (c, d) = (third(triangles[t], (a, b)), third(triangles[u], (b, a)));
+		if crate::incircle(points[a as usize], points[b as usize], points[c as usize], points[d as usize]) <= 0. { continue }
+		// a d b c counterclockwise: a b c, b a d -> a d c, d b c
+		for e in [(a, b), (b, c), (c, a), (b, a), (a, d), (d, b)] { edges.remove(&e); }
+		triangles[t] = [a, d, c]; triangles[u] = [d, b, c];
+		for (e, t) in [((a, d), t), ((d, c), t), ((c, a), t), ((d, b), u), ((b, c), u), ((c, d), u)] { edges.insert(e, t); }
+		stack.extend([(a, d), (d, b), (b, c), (c, a)]);
+	}
+	triangles
+}
+
+#[cfg(test)] mod test {
+	use crate::{xy, vec2, orient2d};
+	use super::{triangulate_into, Predicates};
+	fn p(x: f32, y: f32) -> vec2 { xy{x, y} }
+	#[test] fn counterclockwise() {
+		// Not simple (self touching, self overlapping): no ear at some point, but still only counterclockwise triangles
+		let bowtie = [p(0., 0.), p(2., 2.), p(2., 0.), p(0., 2.)];
+		let touching = [p(0., 0.), p(2., 0.), p(2., 2.), p(1., 0.), p(0., 2.)];
+		let eight = [p(0., 0.), p(1., 0.), p(1., 1.), p(2., 1.), p(2., 2.), p(1., 2.), p(1., 1.), p(0., 1.)];
+		let twisted = [p(0., 0.), p(4., 0.), p(4., 4.), p(2., -1.), p(1., 3.), p(0., 4.)];
+		let spiral = [p(0., 0.), p(3., 0.), p(3., 3.), p(1., 3.), p(1., 1.), p(2., 1.), p(2., 4.), p(0., 4.)];
+		for outer in [&bowtie[..], &touching, &eight, &twisted, &spiral] { for predicates in [Predicates::Fast, Predicates::Exact] {
+			let (mut ring, mut triangles) = ([(0, p(0., 0.)); 8], [[0; 3]; 6]);
+			let count = triangulate_into(outer, &[], predicates, &mut ring, &mut triangles);
+			for &[a, b, c] in &triangles[..count] { assert!(orient2d(outer[a as usize], outer[b as usize], outer[c as usize]) > 0., "{outer:?} {:?}", &triangles[..count]); }
+		} }
+	}
+}
@@ -5,8 +5,6 @@ impl<T:core::fmt::Display> core::fmt::Display for xy<T> {
 	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result { write!(f, "{},{}", self.x, self.y) }
 }
 
-impl<T> xy<T> { pub fn yx(self) -> xy<T> { xy{x: self.y, y: self.x} } }
-
 impl xy<u32> { pub const fn signed(self) -> xy<i32> { xy{x: self.x as i32, y: self.y as i32} } }
 impl xy<i32> { #[track_caller] pub fn try_unsigned(self) -> Option<xy<u32>> { self.map(|s| s.try_into().ok()).transpose() } }
 impl xy<i32> { #[track_caller] pub fn unsigned(self) -> xy<u32> { self.try_unsigned().unwrap() } }
@@ -22,8 +20,11 @@ impl From<xy<f32>> for xy<i32> { fn from(f: xy<f32>) -> Self { xy{x: f.x as i32,
 #[allow(non_camel_case_types)] pub type vec2 = xy<f32>;
 
 pub fn cross2(a: vec2, b: vec2) -> f32 { a.x*b.y - a.y*b.x }
-#[cfg(feature="std")] pub fn atan(v:vec2) -> f32 { v.y.atan2(v.x) }
-#[cfg(feature="std")] pub fn rotate(angle: f32, xy{x,y}: vec2) -> vec2 { let (s,c)=f32::sin_cos(angle); xy{x: c*x - s*y, y: s*x + c*y} }
+// Counterclockwise angle from self to b in (-π, π]
+impl vec2 { pub fn signed_angle(self, b: vec2) -> f32 { cross2(self, b).atan2(crate::dot(self, b)) } }
+#[allow(unused_imports)] use crate::Float; // Float functions are inherent with std
+pub fn atan(v:vec2) -> f32 { v.y.atan2(v.x) }
+pub fn rotate(angle: f32, xy{x,y}: vec2) -> vec2 { let (s,c)=f32::sin_cos(angle); xy{x: c*x - s*y, y: s*x + c*y} }
 
 #[cfg(feature="int_roundings")] use num::Ratio;
 #[cfg(feature="int_roundings")] pub fn ceil(scale: Ratio, v: uint2) -> uint2 { v.map(|c| scale.ceil(c)) }
@@ -58,6 +59,19 @@ impl MinMax<int2> {
 }
 pub type Rect = MinMax<int2>;
 
+impl<T:crate::ComponentWiseMinMax+crate::ComponentWiseLess+Copy> MinMax<xy<T>> {
+	// Up to four disjoint rectangles covering self but not b: full width bands above and below, then left and right of the intersection
+	pub fn difference(self, b: Self) -> impl Iterator<Item=Self> {
+		let bands = if let Some(i) = self.intersection(b) { [
+			MinMax{min: self.min, max: xy{x: self.max.x, y: i.min.y}},
+			MinMax{min: xy{x: self.min.x, y: i.max.y}, max: self.max},
+			MinMax{min: xy{x: self.min.x, y: i.min.y}, max: xy{x: i.min.x, y: i.max.y}},
+			MinMax{min: xy{x: i.max.x, y: i.min.y}, max: xy{x: self.max.x, y: i.max.y}},
+		].map(|r| (!r.is_empty()).then_some(r)) } else { [(!self.is_empty()).then_some(self), None, None, None] };
+		bands.into_iter().flatten()
+	}
+}
+
 use core::ops::{Add,Sub};
 impl Add<Rect> for int2 { type Output=Rect; #[track_caller] fn add(self, r: Rect) -> Self::Output { Rect{min:self+r.min, max:self+r.max} } }
 impl Sub<uint2> for Rect { type Output=Rect; #[track_caller] fn sub(self, b: uint2) -> Self::Output { -b.signed()+self } }
@@ -75,41 +89,135 @@ mod mod_xyz {
 	#[allow(non_camel_case_types)] pub type vec3 = xyz<f32>;
 	impl<T> xyz<T> {
 		pub fn xy_z(super::xy{x,y}: super::xy<T>, z: T) -> Self { xyz{x,y,z} }
-		pub fn xy(self) -> super::xy<T> { let xyz{x,y,..} = self; super::xy{x, y} }
-		pub fn yz(self) -> super::xy<T> { let xyz{y,z,..} = self; super::xy{x: y, y: z} }
-		pub fn zx(self) -> super::xy<T> { let xyz{z,x,..} = self; super::xy{x: z, y: x} }
-		pub fn xz(self) -> super::xy<T> { let xyz{x,z,..} = self; super::xy{x, y: z} }
 	}
 	pub fn cross(a: vec3, b: vec3) -> vec3 { xyz{x: a.y*b.z - a.z*b.y, y: a.z*b.x - a.x*b.z, z: a.x*b.y - a.y*b.x} }
+	impl vec3 {
+		// Right handed angle from self to b around axis, in (-π, π]
+		pub fn signed_angle(self, b: vec3, axis: vec3) -> f32 { let angle = self.angle(b); if crate::dot(cross(self, b), axis) < 0. { -angle } else { angle } }
+		// Tangent and bitangent completing unit self into a right handed orthonormal basis, continuous except at z=0 (Duff et al. 2017)
+		pub fn orthonormal_basis(self) -> (vec3, vec3) {
+			let xyz{x,y,z} = self; let sign = 1f32.copysign(z); let a = -1./(sign + z); let b = x*y*a;
+			(xyz{x: 1. + sign*x*x*a, y: sign*b, z: -sign*x}, xyz{x: b, y: sign + y*y*a, z: -y})
+		}
+	}
 }
 pub use mod_xyz::*;
 
 vector!(4 xyzw T T T T, x y z w, X Y Z W);
 #[allow(non_camel_case_types)] pub type vec4 = xyzw<f32>;
 #[allow(non_camel_case_types)] pub type mat4 = xyzw<vec4>;
+impl<T> xyzw<T> { #[allow(clippy::self_named_constructors)] pub fn xyz_w(xyz{x,y,z}: xyz<T>, w: T) -> Self { xyzw{x,y,z,w} } }
+// Points have w=1 (translated), directions have w=0
+impl<T:num::Zero+From<u8>> xyzw<T> { pub fn point(p: xyz<T>) -> Self { p.xyz1() } pub fn direction(d: xyz<T>) -> Self { d.xyz0() } }
+// Perspective divide by w as is (infinite or NaN for w=0)
+impl<T:core::ops::Div+Copy> xyzw<T> { pub fn homogeneous_divide(self) -> xyz<T::Output> { self.xyz()/self.w } }
+impl vec4 {
+	// None for |w| <= epsilon (points at infinity or on the eye plane)
+	pub fn try_homogeneous_divide(self, epsilon: f32) -> Option<vec3> { (self.w.abs() > epsilon).then(|| self.homogeneous_divide()) }
+	// Divides by w clamped away from zero to ±epsilon, keeping its sign
+	pub fn homogeneous_divide_clamped(self, epsilon: f32) -> vec3 { self.xyz() / if self.w.abs() > epsilon { self.w } else { epsilon.copysign(self.w) } }
+}
 
 use core::array::from_fn as eval;
 pub fn transpose<T: Copy, const M: usize, const N:usize>(m: [[T; N]; M]) -> [[T; M]; N] { eval(|i| eval(|j| m[j][i])) }
-#[allow(non_camel_case_types)] type Matrix<const M: usize, const N:usize> = [[f32; N]; M];
-pub fn mul<const M: usize, const N:usize, const P:usize>(a: Matrix<M,N>, b: Matrix<N,P>) -> Matrix<M,P> { eval(|i| eval(|j| (0..N).map(|k| a[i][k]*b[k][j]).sum())) }
-pub fn mulv<const M: usize, const N:usize>(a: Matrix<M,N>, b: [f32; N]) -> [f32; M] { mul(a, b.map(|k| [k])).map(|[k]| k) }
-//pub fn mulv<const M: usize, const N:usize>(a: Matrix<M,N>, b: [f32; N]) -> [f32; M] { eval(|i| (0..N).map(|k| a[i][k]*b[k]).sum()) }
-pub fn mul1<const M: usize, const N:usize, const P:usize>(a: f32, b: Matrix<N,P>) -> Matrix<M,P> { eval(|i| eval(|j| a*b[i][j])) }
+pub fn mul<const M: usize, const N:usize, const P:usize>(a: [[f32; N]; M], b: [[f32; P]; N]) -> [[f32; P]; M] { eval(|i| eval(|j| (0..N).map(|k| a[i][k]*b[k][j]).sum())) }
+pub fn mulv<const M: usize, const N:usize>(a: [[f32; N]; M], b: [f32; N]) -> [f32; M] { mul(a, b.map(|k| [k])).map(|[k]| k) }
+//pub fn mulv<const M: usize, const N:usize>(a: [[f32; N]; M], b: [f32; N]) -> [f32; M] { eval(|i| (0..N).map(|k| a[i][k]*b[k]).sum()) }
+pub fn mul1<const M: usize, const N:usize, const P:usize>(a: f32, b: [[f32; P]; N]) -> [[f32; P]; M] { eval(|i| eval(|j| a*b[i][j])) }
 
 pub fn diagonal<const N: usize>(diagonal: [f32; N]) -> [[f32; N]; N] { eval(|i| eval(|j| if i==j { diagonal[i]  } else { 0. })) }
 
-#[cfg(feature="generic_const_exprs")] fn minor<const N:usize>(m: [[f32; N]; N], i: usize, j: usize) -> f32 where [[f32; N-1]; N-1]:Det {
-	fn from_iter<T, const N: usize>(mut iter: impl Iterator<Item=T>) -> [T; N] { let a = [(); N].map(|_| iter.next().unwrap()); assert!(iter.next().is_none()); a }
-	det(from_iter(m.into_iter().enumerate().filter(|&(row,_)| row!=i).map(|(_,row)| from_iter(row.into_iter().enumerate().filter(|&(column,_)| column!=j).map(|(_,m)| m)))))
+// Explicit cofactor expansions (stable replacement for a generic_const_exprs minor recursion)
+pub trait Det: Sized {
+	fn det(self) -> f32;
+	fn adjugate(self) -> Self;
+}
+impl Det for [[f32; 1]; 1] {
+	fn det(self) -> f32 { self[0][0] }
+	fn adjugate(self) -> Self { [[1.]] }
+}
+impl Det for [[f32; 2]; 2] {
+	fn det(self) -> f32 { let [[a,b],[c,d]] = self; a*d - b*c }
+	fn adjugate(self) -> Self { let [[a,b],[c,d]] = self; [[d,-b],[-c,a]] }
+}
+impl Det for [[f32; 3]; 3] {
+	fn det(self) -> f32 { let [r0,r1,r2] = self.map(xyz::from); crate::dot(r0, cross(r1,r2)) }
+	fn adjugate(self) -> Self { let [r0,r1,r2] = self.map(xyz::from); transpose([cross(r1,r2), cross(r2,r0), cross(r0,r1)].map(<[f32; 3]>::from)) }
+}
+// 2x2 minors of the upper rows (s) and lower rows (c)
+fn minors([[a00,a01,a02,a03],[a10,a11,a12,a13],[a20,a21,a22,a23],[a30,a31,a32,a33]]: [[f32; 4]; 4]) -> ([f32; 6], [f32; 6]) {(
+	[a00*a11 - a10*a01, a00*a12 - a10*a02, a00*a13 - a10*a03, a01*a12 - a11*a02, a01*a13 - a11*a03, a02*a13 - a12*a03],
+	[a20*a31 - a30*a21, a20*a32 - a30*a22, a20*a33 - a30*a23, a21*a32 - a31*a22, a21*a33 - a31*a23, a22*a33 - a32*a23]
+)}
+impl Det for [[f32; 4]; 4] {
+	fn det(self) -> f32 { let ([s0,s1,s2,s3,s4,s5], [c0,c1,c2,c3,c4,c5]) = minors(self); s0*c5 - s1*c4 + s2*c3 + s3*c2 - s4*c1 + s5*c0 }
+	fn adjugate(self) -> Self {
+		let ([s0,s1,s2,s3,s4,s5], [c0,c1,c2,c3,c4,c5]) = minors(self);
+		let [[a00,a01,a02,a03],[a10,a11,a12,a13],[a20,a21,a22,a23],[a30,a31,a32,a33]] = self;
+		[[ a11*c5 - a12*c4 + a13*c3, -a01*c5 + a02*c4 - a03*c3,  a31*s5 - a32*s4 + a33*s3, -a21*s5 + a22*s4 - a23*s3],
+		 [-a10*c5 + a12*c2 - a13*c1,  a00*c5 - a02*c2 + a03*c1, -a30*s5 + a32*s2 - a33*s1,  a20*s5 - a22*s2 + a23*s1],
+		 [ a10*c4 - a11*c2 + a13*c0, -a00*c4 + a01*c2 - a03*c0,  a30*s4 - a31*s2 + a33*s0, -a20*s4 + a21*s2 - a23*s0],
+		 [-a10*c3 + a11*c1 - a12*c0,  a00*c3 - a01*c1 + a02*c0, -a30*s3 + a31*s1 - a32*s0,  a20*s3 - a21*s1 + a22*s0]]
+	}
+}
+pub fn det<M: Det>(m: M) -> f32 { m.det() }
+pub fn adjugate<M: Det>(m: M) -> M { m.adjugate() }
+// None if singular (or if the determinant overflows)
+pub fn inverse<const N:usize>(m: [[f32; N]; N]) -> Option<[[f32; N]; N]> where [[f32; N]; N]:Det {
+	let det = det(m);
+	(det != 0. && det.is_finite()).then(|| adjugate(m).map(|row| row.map(|a| a/det)))
+}
+
+#[cfg(test)] mod test {
+	use super::{det, inverse, mul, diagonal, xy, Rect};
+	use crate::MinMax;
+	fn assert_identity<const N: usize>(m: [[f32; N]; N]) { for (i, row) in m.into_iter().enumerate() { for (j, a) in row.into_iter().enumerate() { assert!((a - if i==j { 1. } else { 0. }).abs() < 1e-5, "{m:?}"); } } }
+	#[test] fn inverse_identity() {
+		let m2 = [[4., 7.], [2., 6.]]; assert_eq!(det(m2), 10.); assert_identity(mul(m2, inverse(m2).unwrap()));
+		let m3 = [[2., -1., 0.], [-1., 2., -1.], [0., -1., 2.]]; assert_eq!(det(m3), 4.); assert_identity(mul(m3, inverse(m3).unwrap()));
+		let m4 = [[1., 2., 0., 1.], [0., 1., 3., 0.], [2., 0., 1., 1.], [1., 1., 0., 2.]]; assert_identity(mul(m4, inverse(m4).unwrap())); assert_identity(mul(inverse(m4).unwrap(), m4));
+		assert_eq!(det(m4), det(super::transpose(m4))); assert_eq!(det(diagonal([2., 3., 4., 5.])), 120.);
+	}
+	#[test] fn singular() {
+		assert_eq!(inverse([[1., 2.], [2., 4.]]), None); assert_eq!(inverse([[1., 2., 3.], [4., 5., 6.], [7., 8., 9.]]), None);
+		assert_eq!(inverse([[1., 2., 3., 4.], [2., 4., 6., 8.], [0., 1., 0., 1.], [1., 0., 1., 0.]]), None); assert_eq!(inverse([[0f32; 4]; 4]), None);
+	}
+	#[test] fn non_finite() {
+		assert_eq!(inverse([[f32::NAN, 0.], [0., 1.]]), None); assert_eq!(inverse([[f32::INFINITY, 0., 0.], [0., 1., 0.], [0., 0., 1.]]), None);
+		assert_eq!(inverse(diagonal([1e30f32, 1e30, 1e30, 1.])), None); // Determinant overflows
+		assert!(det([[f32::NAN; 4]; 4]).is_nan());
+	}
+	fn r(x0: i32, y0: i32, x1: i32, y1: i32) -> Rect { Rect{min: xy{x: x0, y: y0}, max: xy{x: x1, y: y1}} }
+	#[test] fn rect() {
+		let a = r(0, 0, 10, 10);
+		assert!(!a.is_empty()); assert!(r(0, 0, 0, 10).is_empty()); assert!(r(5, 0, 0, 10).is_empty()); assert!(MinMax{min: 1., max: 1.}.is_empty());
+		assert_eq!(a.intersection(r(5, -5, 15, 5)), Some(r(5, 0, 10, 5))); assert_eq!(a.intersection(r(2, 2, 4, 4)), Some(r(2, 2, 4, 4)));
+		assert_eq!(a.intersection(r(10, 0, 15, 5)), None); assert!(!a.intersects(r(10, 0, 15, 5))); assert!(!a.intersects(r(0, 10, 10, 20))); // Touching edges
+		assert!(!a.intersects(r(3, 3, 3, 7))); assert!(!r(3, 3, 3, 7).intersects(a)); assert!(a.intersects(r(9, 9, 20, 20)));
+		assert!(a.contains_rect(&a)); assert!(a.contains_rect(&r(1, 1, 9, 10))); assert!(!a.contains_rect(&r(1, 1, 11, 10))); assert!(!a.contains_rect(&r(-1, 0, 5, 5)));
+		assert!(a.contains_rect(&r(20, 20, 20, 20))); assert!(!r(5, 5, 5, 5).contains_rect(&a)); // Empty rects are contained anywhere and contain nothing
+		assert!(MinMax{min: 0, max: 3}.intersects(MinMax{min: 2, max: 4})); assert!(!MinMax{min: 0, max: 3}.intersects(MinMax{min: 3, max: 4}));
+	}
+	#[test] fn difference() {
+		let a = r(0, 0, 10, 10);
+		assert!(a.difference(r(2, 3, 4, 5)).eq([r(0, 0, 10, 3), r(0, 5, 10, 10), r(0, 3, 2, 5), r(4, 3, 10, 5)]));
+		assert_eq!(a.difference(r(2, 3, 4, 5)).map(|r| r.area()).sum::<u32>(), 100-4);
+		assert!(a.difference(r(5, -1, 11, 11)).eq([r(0, 0, 5, 10)])); assert!(a.difference(r(0, 0, 10, 4)).eq([r(0, 4, 10, 10)]));
+		assert_eq!(a.difference(a).count(), 0); assert_eq!(a.difference(r(-1, -1, 11, 11)).count(), 0); // Contained
+		assert!(a.difference(r(10, 0, 20, 10)).eq([a])); assert!(a.difference(r(20, 20, 30, 30)).eq([a])); // Touching or disjoint
+		assert!(a.difference(r(5, 5, 5, 5)).eq([a])); assert_eq!(r(5, 5, 5, 8).difference(a).count(), 0); // Empty
+	}
+	#[test] fn homogeneous_divide() {
+		use crate::{xyz, xyzw, vec3, mat3, mat4x4};
+		let p = xyzw{x: 2., y: -4., z: 6., w: 2.}; assert_eq!(p.homogeneous_divide(), xyz{x: 1., y: -2., z: 3.}); assert_eq!(p.try_homogeneous_divide(1e-6), Some(xyz{x: 1., y: -2., z: 3.}));
+		let behind = xyzw{x: 2., y: -4., z: 6., w: -2.}; assert_eq!(behind.homogeneous_divide(), xyz{x: -1., y: 2., z: -3.}); assert_eq!(behind.homogeneous_divide_clamped(1e-3), xyz{x: -1., y: 2., z: -3.});
+		let infinite = xyzw{x: 1., y: 0., z: -1., w: 0.}; let q: vec3 = infinite.homogeneous_divide(); assert_eq!((q.x, q.z), (f32::INFINITY, f32::NEG_INFINITY)); assert!(q.y.is_nan());
+		assert_eq!(infinite.try_homogeneous_divide(0.), None); assert_eq!(p.with_w(1e-7).try_homogeneous_divide(1e-6), None); assert_eq!(p.with_w(-1e-7).try_homogeneous_divide(1e-6), None);
+		// Clamped to ±epsilon keeping the sign of w (+0 as positive)
+		assert_eq!(infinite.homogeneous_divide_clamped(0.5), xyz{x: 2., y: 0., z: -2.}); assert_eq!(infinite.with_w(-0.).homogeneous_divide_clamped(0.5), xyz{x: -2., y: -0., z: 2.});
+		assert_eq!(p.with_w(0.25).homogeneous_divide_clamped(0.5), xyz{x: 4., y: -8., z: 12.}); assert_eq!(p.with_w(-0.25).homogeneous_divide_clamped(0.5), xyz{x: -4., y: 8., z: -12.});
+		assert_eq!(p.with_w(4.).homogeneous_divide_clamped(0.5), xyz{x: 0.5, y: -1., z: 1.5});
+		assert_eq!(xyzw::point(xyz{x: 1., y: 2., z: 3.}), xyzw{x: 1., y: 2., z: 3., w: 1.}); assert_eq!(xyzw::direction(xyz{x: 1, y: 2, z: 3}), xyzw{x: 1, y: 2, z: 3, w: 0});
+		let m = mat3::rotation_z(0.5); assert_eq!(mat3::from(mat4x4::from(m)), m);
+	}
 }
-#[cfg(feature="generic_const_exprs")] fn cofactor<const N:usize>(m: [[f32; N]; N], i: usize, j: usize) -> f32 where [[f32; N-1]; N-1]:Det { (match (i+j)%2 { 0=>1., 1=>-1., _=>unreachable!()})*minor(m,i,j) }
-#[cfg(feature="generic_const_exprs")] fn det_n<const N:usize>(m: [[f32; N]; N]) -> f32 where [[f32; N-1]; N-1]:Det { (0..N).map(|j| m[0][j]*cofactor(m, 0,j)).sum() }
-#[cfg(feature="generic_const_exprs")] trait Det { fn det(self) -> f32; }
-#[cfg(feature="generic_const_exprs")] impl Det for [[f32; 3]; 3] { fn det(self) -> f32 { det_n(self) }}
-#[cfg(feature="generic_const_exprs")] impl Det for [[f32; 2]; 2] { fn det(self) -> f32 { det_n(self) }}
-#[cfg(feature="generic_const_exprs")] impl Det for [[f32; 1]; 1] { fn det(self) -> f32 { self[0][0] }}
-#[cfg(feature="generic_const_exprs")] fn det<T: Det>(t: T) -> f32 { t.det() }
-#[cfg(feature="generic_const_exprs")] fn adjugate<const N:usize>(m: [[f32; N]; N]) -> [[f32; N]; N] where [[f32; N-1]; N-1]:Det { eval(|i| eval(|j| cofactor(m, j,i)) ) }
-#[cfg(feature="generic_const_exprs")] #[allow(private_bounds)] pub fn inverse<const N:usize>(m: [[f32; N]; N]) -> [[f32; N]; N] where [[f32; N]; N]:Det, [[f32; N-1]; N-1]:Det { mul1(1./det(m), adjugate(m)) }
-
-#[allow(non_camel_case_types)] pub type mat3 = Matrix<3,3>;